@@ -50,7 +50,7 @@ fn setup(
             0.0,
         )
         .with_rotation(Quat::from_rotation_x(-PI / 4.));
-        let mesh = mesh_with_transform(shape, &trans).unwrap();
+        let mesh = mesh_with_transform(shape, &trans);
         mesh_append(&mut combined_mesh, &mesh).unwrap();
     }
 
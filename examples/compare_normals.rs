@@ -15,8 +15,8 @@ fn setup(
     let cube_mesh = Cuboid::default().mesh().into();
     let sphere_mesh = Sphere::default().mesh().uv(32, 18);
 
-    let mut mesh_a = mesh_with_transform(&cube_mesh, &Transform::from_xyz(-2.0, 0.0, 0.0)).unwrap();
-    let mesh_b = mesh_with_transform(&sphere_mesh, &Transform::from_xyz(2.0, 0.0, 0.0)).unwrap();
+    let mut mesh_a = mesh_with_transform(&cube_mesh, &Transform::from_xyz(-2.0, 0.0, 0.0));
+    let mesh_b = mesh_with_transform(&sphere_mesh, &Transform::from_xyz(2.0, 0.0, 0.0));
 
     mesh_append(&mut mesh_a, &mesh_b).unwrap();
 
@@ -63,7 +63,7 @@ fn rotate(
         m.transform.rotate_x(r1);
         m.transform.rotate_z(r2);
         if let Some(mesh) = meshes.get_mut(&*mesh_h) {
-            *mesh = mesh_with_transform(&m.mesh, &m.transform).unwrap();
+            *mesh = mesh_with_transform(&m.mesh, &m.transform);
         }
     }
     for mut trans in &mut rotate_only {
@@ -1,19 +1,31 @@
-use std::slice::{Iter, IterMut};
+use std::{
+    collections::{BinaryHeap, HashMap, HashSet},
+    io::{self, BufRead, Write},
+    ops::Range,
+    slice::{Iter, IterMut},
+};
 
 use bevy::{
-    math::Vec4Swizzles,
+    math::{DMat4, Vec4Swizzles},
     prelude::*,
     render::{
         mesh::{
             skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
-            Indices, MeshVertexAttributeId, VertexAttributeValues,
+            GenerateTangentsError, Indices, MeshVertexAttribute, MeshVertexAttributeId,
+            VertexAttributeValues,
         },
         render_asset::RenderAssetUsages,
-        render_resource::PrimitiveTopology,
+        render_resource::{PrimitiveTopology, VertexFormat},
     },
 };
 use thiserror::Error;
 
+// The accessors reinterpret `[f32; N]` attribute data as glam vectors, which relies on them
+// having the same layout.
+const _: () = assert!(std::mem::size_of::<Vec2>() == std::mem::size_of::<[f32; 2]>());
+const _: () = assert!(std::mem::size_of::<Vec3>() == std::mem::size_of::<[f32; 3]>());
+const _: () = assert!(std::mem::size_of::<Vec4>() == std::mem::size_of::<[f32; 4]>());
+
 #[inline]
 pub fn mesh_len(mesh: &Mesh) -> usize {
     match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
@@ -22,12 +34,22 @@ pub fn mesh_len(mesh: &Mesh) -> usize {
     }
 }
 
-pub fn mesh_joint_weights(mesh: &Mesh) -> Iter<Vec4> {
-    match mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT) {
-        Some(VertexAttributeValues::Float32x4(v)) => unsafe {
-            std::mem::transmute::<Iter<[f32; 4]>, Iter<Vec4>>(v.iter())
-        },
-        _ => [].iter(),
+// Vec4 can be 16 byte aligned while [f32; 4] is only 4 byte aligned, so Float32x4 attributes
+// can't be reinterpreted as Vec4s. Their accessors copy each value out instead, and the mutable
+// ones hand out the arrays.
+
+pub fn mesh_joint_weights(mesh: &Mesh) -> impl ExactSizeIterator<Item = Vec4> + '_ {
+    let weights = match mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT) {
+        Some(VertexAttributeValues::Float32x4(v)) => v.as_slice(),
+        _ => &[],
+    };
+    weights.iter().map(|w| Vec4::from(*w))
+}
+
+pub fn mesh_joint_weights_mut(mesh: &mut Mesh) -> IterMut<[f32; 4]> {
+    match mesh.attribute_mut(Mesh::ATTRIBUTE_JOINT_WEIGHT) {
+        Some(VertexAttributeValues::Float32x4(v)) => v.iter_mut(),
+        _ => [].iter_mut(),
     }
 }
 
@@ -38,86 +60,194 @@ pub fn mesh_joint_indices(mesh: &Mesh) -> Iter<[u16; 4]> {
     }
 }
 
+pub fn mesh_joint_indices_mut(mesh: &mut Mesh) -> IterMut<[u16; 4]> {
+    match mesh.attribute_mut(Mesh::ATTRIBUTE_JOINT_INDEX) {
+        Some(VertexAttributeValues::Uint16x4(indices)) => indices.iter_mut(),
+        _ => [].iter_mut(),
+    }
+}
+
 pub fn mesh_positions(mesh: &Mesh) -> Iter<Vec3> {
+    mesh_positions_slice(mesh).iter()
+}
+
+pub fn mesh_positions_slice(mesh: &Mesh) -> &[Vec3] {
     match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
-        Some(VertexAttributeValues::Float32x3(v)) => unsafe {
-            std::mem::transmute::<Iter<[f32; 3]>, Iter<Vec3>>(v.iter())
-        },
-        _ => [].iter(),
+        Some(VertexAttributeValues::Float32x3(v)) => bytemuck::cast_slice::<[f32; 3], Vec3>(v),
+        _ => &[],
     }
 }
 
 pub fn mesh_positions_mut(mesh: &mut Mesh) -> IterMut<Vec3> {
     match mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
-        Some(VertexAttributeValues::Float32x3(v)) => unsafe {
-            std::mem::transmute::<IterMut<[f32; 3]>, IterMut<Vec3>>(v.iter_mut())
-        },
+        Some(VertexAttributeValues::Float32x3(v)) => {
+            bytemuck::cast_slice_mut::<[f32; 3], Vec3>(v).iter_mut()
+        }
         _ => [].iter_mut(),
     }
 }
 
 pub fn mesh_normals(mesh: &Mesh) -> Iter<Vec3> {
+    mesh_normals_slice(mesh).iter()
+}
+
+pub fn mesh_normals_slice(mesh: &Mesh) -> &[Vec3] {
     match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
-        Some(VertexAttributeValues::Float32x3(v)) => unsafe {
-            std::mem::transmute::<Iter<[f32; 3]>, Iter<Vec3>>(v.iter())
-        },
-        _ => [].iter(),
+        Some(VertexAttributeValues::Float32x3(v)) => bytemuck::cast_slice::<[f32; 3], Vec3>(v),
+        _ => &[],
     }
 }
 
 pub fn mesh_normals_mut(mesh: &mut Mesh) -> IterMut<Vec3> {
     match mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL) {
-        Some(VertexAttributeValues::Float32x3(v)) => unsafe {
-            std::mem::transmute::<IterMut<[f32; 3]>, IterMut<Vec3>>(v.iter_mut())
-        },
+        Some(VertexAttributeValues::Float32x3(v)) => {
+            bytemuck::cast_slice_mut::<[f32; 3], Vec3>(v).iter_mut()
+        }
         _ => [].iter_mut(),
     }
 }
 
-pub fn mesh_tangents(mesh: &Mesh) -> Iter<Vec4> {
-    match mesh.attribute(Mesh::ATTRIBUTE_TANGENT) {
-        Some(VertexAttributeValues::Float32x4(v)) => unsafe {
-            std::mem::transmute::<Iter<[f32; 4]>, Iter<Vec4>>(v.iter())
-        },
-        _ => [].iter(),
-    }
+pub fn mesh_tangents(mesh: &Mesh) -> impl ExactSizeIterator<Item = Vec4> + '_ {
+    let tangents = match mesh.attribute(Mesh::ATTRIBUTE_TANGENT) {
+        Some(VertexAttributeValues::Float32x4(v)) => v.as_slice(),
+        _ => &[],
+    };
+    tangents.iter().map(|t| Vec4::from(*t))
 }
 
-pub fn mesh_tangents_mut(mesh: &mut Mesh) -> IterMut<Vec4> {
+pub fn mesh_tangents_mut(mesh: &mut Mesh) -> IterMut<[f32; 4]> {
     match mesh.attribute_mut(Mesh::ATTRIBUTE_TANGENT) {
-        Some(VertexAttributeValues::Float32x4(v)) => unsafe {
-            std::mem::transmute::<IterMut<[f32; 4]>, IterMut<Vec4>>(v.iter_mut())
-        },
+        Some(VertexAttributeValues::Float32x4(v)) => v.iter_mut(),
         _ => [].iter_mut(),
     }
 }
 
 pub fn mesh_uvs(mesh: &Mesh) -> Iter<Vec2> {
+    mesh_uvs_slice(mesh).iter()
+}
+
+pub fn mesh_uvs_slice(mesh: &Mesh) -> &[Vec2] {
     match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
-        Some(VertexAttributeValues::Float32x2(v)) => unsafe {
-            std::mem::transmute::<Iter<[f32; 2]>, Iter<Vec2>>(v.iter())
-        },
-        _ => [].iter(),
+        Some(VertexAttributeValues::Float32x2(v)) => bytemuck::cast_slice::<[f32; 2], Vec2>(v),
+        _ => &[],
     }
 }
 
 pub fn mesh_uvs_mut(mesh: &mut Mesh) -> IterMut<Vec2> {
     match mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0) {
-        Some(VertexAttributeValues::Float32x2(v)) => unsafe {
-            std::mem::transmute::<IterMut<[f32; 2]>, IterMut<Vec2>>(v.iter_mut())
-        },
+        Some(VertexAttributeValues::Float32x2(v)) => {
+            bytemuck::cast_slice_mut::<[f32; 2], Vec2>(v).iter_mut()
+        }
+        _ => [].iter_mut(),
+    }
+}
+
+pub fn mesh_uvs1(mesh: &Mesh) -> Iter<Vec2> {
+    match mesh.attribute(Mesh::ATTRIBUTE_UV_1) {
+        Some(VertexAttributeValues::Float32x2(v)) => {
+            bytemuck::cast_slice::<[f32; 2], Vec2>(v).iter()
+        }
+        _ => [].iter(),
+    }
+}
+
+pub fn mesh_uvs1_mut(mesh: &mut Mesh) -> IterMut<Vec2> {
+    match mesh.attribute_mut(Mesh::ATTRIBUTE_UV_1) {
+        Some(VertexAttributeValues::Float32x2(v)) => {
+            bytemuck::cast_slice_mut::<[f32; 2], Vec2>(v).iter_mut()
+        }
         _ => [].iter_mut(),
     }
 }
 
-pub fn mesh_with_transform(mesh: &Mesh, transform: &Transform) -> Option<Mesh> {
+/// Reads `Mesh::ATTRIBUTE_COLOR` as normalized `Vec4`s, converting from `Float32x4`, `Unorm8x4`
+/// or `Uint8x4` storage. Unlike the other accessors this allocates instead of returning a
+/// zero-copy iterator.
+pub fn mesh_colors(mesh: &Mesh) -> Vec<Vec4> {
+    match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+        Some(VertexAttributeValues::Float32x4(v)) => v.iter().map(|c| Vec4::from(*c)).collect(),
+        Some(VertexAttributeValues::Unorm8x4(v)) | Some(VertexAttributeValues::Uint8x4(v)) => v
+            .iter()
+            .map(|c| Vec4::from(c.map(|x| x as f32)) / 255.0)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Writes `colors` to `Mesh::ATTRIBUTE_COLOR`, keeping the existing storage format if there is
+/// one and inserting a `Float32x4` attribute otherwise.
+pub fn mesh_set_colors(mesh: &mut Mesh, colors: &[Vec4]) {
+    match mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR) {
+        Some(VertexAttributeValues::Unorm8x4(v)) | Some(VertexAttributeValues::Uint8x4(v)) => {
+            *v = colors
+                .iter()
+                .map(|c| {
+                    (c.clamp(Vec4::ZERO, Vec4::ONE) * 255.0)
+                        .round()
+                        .to_array()
+                        .map(|x| x as u8)
+                })
+                .collect();
+        }
+        _ => {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_COLOR,
+                colors.iter().map(|c| c.to_array()).collect::<Vec<_>>(),
+            );
+        }
+    }
+}
+
+pub fn mesh_with_transform(mesh: &Mesh, transform: &Transform) -> Mesh {
+    mesh_with_matrix(mesh, &transform.compute_matrix())
+}
+
+#[derive(Error, Debug)]
+pub enum MeshTransformError {
+    #[error("Mesh has no Float32x3 position attribute.")]
+    MissingPositions,
+}
+
+/// Same as [`mesh_with_transform`] but returns an error instead of silently returning an
+/// unchanged clone when the mesh has no positions.
+pub fn try_mesh_with_transform(
+    mesh: &Mesh,
+    transform: &Transform,
+) -> Result<Mesh, MeshTransformError> {
+    match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(_)) => Ok(mesh_with_transform(mesh, transform)),
+        _ => Err(MeshTransformError::MissingPositions),
+    }
+}
+
+/// Same as [`mesh_with_transform`] but takes the model matrix directly.
+pub fn mesh_with_matrix(mesh: &Mesh, mat: &Mat4) -> Mesh {
     let mut mesh = mesh.clone();
+    transform_mesh_with_matrix_in_place(&mut mesh, mat);
+    mesh
+}
 
-    let model = transform.compute_matrix();
+/// Same as [`mesh_with_transform`] but for world space baking from a `GlobalTransform`. Uses the
+/// affine directly so sheared transforms aren't lost by decomposing into a `Transform`.
+pub fn mesh_with_global_transform(mesh: &Mesh, gt: &GlobalTransform) -> Mesh {
+    mesh_with_matrix(mesh, &Mat4::from(gt.affine()))
+}
+
+/// Bakes `transform` into the positions, normals and tangents of `mesh` without cloning it.
+pub fn transform_mesh_in_place(mesh: &mut Mesh, transform: &Transform) {
+    transform_mesh_with_matrix_in_place(mesh, &transform.compute_matrix());
+}
+
+/// Same as [`transform_mesh_in_place`] but takes the model matrix directly.
+///
+/// Matrices with a negative determinant, such as a negative scale, also flip the triangle winding
+/// so the mesh isn't rendered inside out.
+pub fn transform_mesh_with_matrix_in_place(mesh: &mut Mesh, mat: &Mat4) {
+    let model = *mat;
 
-    for p in mesh_positions_mut(&mut mesh) {
+    for_each_vec3_mut(mesh, Mesh::ATTRIBUTE_POSITION, |p| {
         *p = model.transform_point3(*p);
-    }
+    });
 
     // Comment below taken from mesh_normal_local_to_world() in mesh_functions.wgsl regarding
     // transform normals from local to world coordinates:
@@ -131,9 +261,9 @@ pub fn mesh_with_transform(mesh: &Mesh, transform: &Transform) -> Option<Mesh> {
 
     let inverse_transpose_model = Mat3::from_mat4(model.inverse().transpose());
 
-    for n in mesh_normals_mut(&mut mesh) {
+    for_each_vec3_mut(mesh, Mesh::ATTRIBUTE_NORMAL, |n| {
         *n = inverse_transpose_model.mul_vec3(*n).normalize_or_zero();
-    }
+    });
 
     // Comment below taken from mesh_tangent_local_to_world() in mesh_functions.wgsl regarding
     // transform normals from local to world coordinates:
@@ -149,25 +279,119 @@ pub fn mesh_with_transform(mesh: &Mesh, transform: &Transform) -> Option<Mesh> {
 
     let sign_determinant_positive = model.determinant().is_sign_positive();
 
-    for tangent in mesh_tangents_mut(&mut mesh) {
+    // Reflections turn the triangles inside out, flip them back so the same side stays visible
+    if !sign_determinant_positive {
+        flip_winding(mesh);
+    }
+
+    for tangent in mesh_tangents_mut(mesh) {
+        let [x, y, z, mut w] = *tangent;
+        if !sign_determinant_positive {
+            w *= -1.0;
+        }
         *tangent = model
-            .mul_vec3(tangent.xyz())
+            .mul_vec3(Vec3::new(x, y, z))
             .normalize_or_zero()
-            .extend(tangent.w);
-        if !sign_determinant_positive {
-            tangent.w *= -1.0;
+            .extend(w)
+            .to_array();
+    }
+}
+
+/// Reflects `mesh` across the plane through `plane_point` with normal `plane_normal`. Normals and
+/// tangents are reflected too and, as for any transform with a negative determinant, the triangle
+/// winding is flipped so the mirrored copy still faces outward.
+pub fn mirror_mesh(mesh: &mut Mesh, plane_point: Vec3, plane_normal: Vec3) {
+    let n = plane_normal.normalize_or_zero();
+    // Householder reflection through the plane
+    let reflection = Mat3::IDENTITY - Mat3::from_cols(n * n.x, n * n.y, n * n.z) * 2.0;
+    let mat = Mat4::from_translation(2.0 * n.dot(plane_point) * n) * Mat4::from_mat3(reflection);
+    transform_mesh_with_matrix_in_place(mesh, &mat);
+}
+
+/// Reverses the vertex order of every triangle in a `TriangleList` mesh, swapping which side is
+/// front facing. Normals are left unchanged. Other topologies are left unchanged.
+pub fn flip_winding(mesh: &mut Mesh) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    match mesh.indices_mut() {
+        Some(Indices::U16(indices)) => {
+            for tri in indices.chunks_exact_mut(3) {
+                tri.swap(1, 2);
+            }
+        }
+        Some(Indices::U32(indices)) => {
+            for tri in indices.chunks_exact_mut(3) {
+                tri.swap(1, 2);
+            }
+        }
+        None => {
+            let mut order = (0..mesh.count_vertices()).collect::<Vec<_>>();
+            for tri in order.chunks_exact_mut(3) {
+                tri.swap(1, 2);
+            }
+            mesh_select_vertices(mesh, &order);
         }
     }
+}
 
-    Some(mesh)
+/// Negates every normal. Tangent handedness is flipped too so the bitangent, and with it normal
+/// mapping, is unchanged. Usually paired with [`flip_winding`].
+pub fn flip_normals(mesh: &mut Mesh) {
+    for n in mesh_normals_mut(mesh) {
+        *n = -*n;
+    }
+    for tangent in mesh_tangents_mut(mesh) {
+        tangent[3] = -tangent[3];
+    }
+}
+
+/// Appends a copy of `mesh` with flipped winding and normals, so both sides render with backface
+/// culling enabled.
+pub fn make_double_sided(mesh: &Mesh) -> Mesh {
+    let mut back = mesh.clone();
+    flip_winding(&mut back);
+    flip_normals(&mut back);
+    let mut double_sided = mesh.clone();
+    // Both sides have the same attributes and topology
+    let _ = mesh_append(&mut double_sided, &back);
+    double_sided
+}
+
+/// Applies `f` to every value of a `Float32x3` attribute, in parallel when the `rayon` feature is
+/// enabled.
+fn for_each_vec3_mut(
+    mesh: &mut Mesh,
+    attribute: impl Into<MeshVertexAttributeId>,
+    f: impl Fn(&mut Vec3) + Send + Sync,
+) {
+    if let Some(VertexAttributeValues::Float32x3(v)) = mesh.attribute_mut(attribute) {
+        let v = bytemuck::cast_slice_mut::<[f32; 3], Vec3>(v);
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            v.par_iter_mut().for_each(f);
+        }
+        #[cfg(not(feature = "rayon"))]
+        v.iter_mut().for_each(f);
+    }
 }
 
 #[inline]
 pub fn skin_model(joint_matrices: &[Mat4], indexes: &[u16; 4], weights: &Vec4) -> Mat4 {
-    weights.x * joint_matrices[indexes[0] as usize]
-        + weights.y * joint_matrices[indexes[1] as usize]
-        + weights.z * joint_matrices[indexes[2] as usize]
-        + weights.w * joint_matrices[indexes[3] as usize]
+    skin_model_slice(joint_matrices, indexes, &weights.to_array())
+}
+
+/// Same as [`skin_model`] but for any number of influences, such as two sets of four joints
+/// concatenated together.
+#[inline]
+pub fn skin_model_slice(joint_matrices: &[Mat4], indexes: &[u16], weights: &[f32]) -> Mat4 {
+    indexes
+        .iter()
+        .zip(weights)
+        .fold(Mat4::ZERO, |model, (index, weight)| {
+            model + *weight * joint_matrices[*index as usize]
+        })
 }
 
 #[inline]
@@ -177,17 +401,32 @@ pub fn skinned_mesh_joints(
     joints: &Query<&GlobalTransform>,
 ) -> Option<Vec<Mat4>> {
     let mut buffer = Vec::new();
-    let inverse_bindposes = inverse_bindposes.get(&skin.inverse_bindposes)?;
+    skinned_mesh_joints_into(skin, inverse_bindposes, joints, &mut buffer).then_some(buffer)
+}
+
+/// Same as [`skinned_mesh_joints`] but clears and fills `buffer` instead of allocating, so it can
+/// be reused across calls. Returns `false` if the joints aren't available.
+#[inline]
+pub fn skinned_mesh_joints_into(
+    skin: &SkinnedMesh,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    joints: &Query<&GlobalTransform>,
+    buffer: &mut Vec<Mat4>,
+) -> bool {
+    buffer.clear();
+    let Some(inverse_bindposes) = inverse_bindposes.get(&skin.inverse_bindposes) else {
+        return false;
+    };
 
     for (inverse_bindpose, joint) in inverse_bindposes.iter().zip(skin.joints.iter()) {
         if let Ok(joint) = joints.get(*joint) {
             buffer.push(joint.affine() * *inverse_bindpose);
         } else {
-            return None;
+            return false;
         }
     }
 
-    Some(buffer)
+    true
 }
 
 pub fn mesh_with_skinned_transform(
@@ -200,92 +439,206 @@ pub fn mesh_with_skinned_transform(
 
     // get skinned mesh joint models
     if let Some(joints) = skinned_mesh_joints(skinned_mesh, inverse_bindposes, joint_query) {
-        let mut models = Vec::with_capacity(mesh_len(mesh));
-        // Use skin model to get world space vertex positions
-        for ((pos, indices), weights) in mesh_positions_mut(&mut new_mesh)
-            .zip(mesh_joint_indices(mesh))
-            .zip(mesh_joint_weights(mesh))
-        {
-            let model = skin_model(&joints, indices, weights);
-            *pos = model.transform_point3(*pos);
-            models.push(Mat3::from_mat4(model));
-        }
-
-        // Comment below taken from mesh_normal_local_to_world() in mesh_functions.wgsl regarding
-        // transform normals from local to world coordinates:
-
-        // NOTE: The mikktspace method of normal mapping requires that the world normal is
-        // re-normalized in the vertex shader to match the way mikktspace bakes vertex tangents
-        // and normal maps so that the exact inverse process is applied when shading. Blender, Unity,
-        // Unreal Engine, Godot, and more all use the mikktspace method. Do not change this code
-        // unless you really know what you are doing.
-        // http://www.mikktspace.com/
-
-        for (normal, model) in mesh_normals_mut(&mut new_mesh).zip(&models) {
-            let inverse_transpose_model = model.inverse().transpose();
-            *normal = inverse_transpose_model
-                .mul_vec3(*normal)
-                .normalize_or_zero();
-        }
-
-        // Comment below taken from mesh_tangent_local_to_world() in mesh_functions.wgsl regarding
-        // transform normals from local to world coordinates:
-
-        // NOTE: The mikktspace method of normal mapping requires that the world tangent is
-        // re-normalized in the vertex shader to match the way mikktspace bakes vertex tangents
-        // and normal maps so that the exact inverse process is applied when shading. Blender, Unity,
-        // Unreal Engine, Godot, and more all use the mikktspace method. Do not change this code
-        // unless you really know what you are doing.
-        // http://www.mikktspace.com/
-        for (tangent, model) in mesh_tangents_mut(&mut new_mesh).zip(&models) {
-            *tangent = model
-                .mul_vec3(tangent.xyz())
-                .normalize_or_zero()
-                .extend(tangent.w);
+        skin_mesh_in_place(&mut new_mesh, &joints);
+    }
+
+    Some(new_mesh)
+}
+
+/// Skins the positions, normals and tangents of `mesh` in place using joint matrices from
+/// [`skinned_mesh_joints`], which can be computed once and shared by meshes with the same
+/// skeleton.
+pub fn skin_mesh_in_place(mesh: &mut Mesh, joints: &[Mat4]) {
+    let skin_models = mesh_joint_indices(mesh)
+        .zip(mesh_joint_weights(mesh))
+        .map(|(indices, weights)| skin_model(joints, indices, &weights))
+        .collect::<Vec<_>>();
+
+    let mut models = Vec::with_capacity(skin_models.len());
+    // Use skin model to get world space vertex positions
+    for (pos, model) in mesh_positions_mut(mesh).zip(&skin_models) {
+        *pos = model.transform_point3(*pos);
+        models.push(Mat3::from_mat4(*model));
+    }
+
+    // Comment below taken from mesh_normal_local_to_world() in mesh_functions.wgsl regarding
+    // transform normals from local to world coordinates:
+
+    // NOTE: The mikktspace method of normal mapping requires that the world normal is
+    // re-normalized in the vertex shader to match the way mikktspace bakes vertex tangents
+    // and normal maps so that the exact inverse process is applied when shading. Blender, Unity,
+    // Unreal Engine, Godot, and more all use the mikktspace method. Do not change this code
+    // unless you really know what you are doing.
+    // http://www.mikktspace.com/
+
+    for (normal, model) in mesh_normals_mut(mesh).zip(&models) {
+        let inverse_transpose_model = model.inverse().transpose();
+        *normal = inverse_transpose_model
+            .mul_vec3(*normal)
+            .normalize_or_zero();
+    }
 
-            // TODO does the transform need to be included to do this?
-            // NOTE: Multiplying by the sign of the determinant of the 3x3 model matrix accounts for
-            // situations such as negative scaling.
-            //if !Mat3A::from_mat4(transform).determinant().is_sign_positive() {
-            //    tangent.w *= -1.0;
-            //}
+    // Comment below taken from mesh_tangent_local_to_world() in mesh_functions.wgsl regarding
+    // transform normals from local to world coordinates:
+
+    // NOTE: The mikktspace method of normal mapping requires that the world tangent is
+    // re-normalized in the vertex shader to match the way mikktspace bakes vertex tangents
+    // and normal maps so that the exact inverse process is applied when shading. Blender, Unity,
+    // Unreal Engine, Godot, and more all use the mikktspace method. Do not change this code
+    // unless you really know what you are doing.
+    // http://www.mikktspace.com/
+    for (tangent, model) in mesh_tangents_mut(mesh).zip(&models) {
+        let [x, y, z, w] = *tangent;
+        *tangent = model
+            .mul_vec3(Vec3::new(x, y, z))
+            .normalize_or_zero()
+            .extend(w)
+            .to_array();
+
+        // TODO does the transform need to be included to do this?
+        // NOTE: Multiplying by the sign of the determinant of the 3x3 model matrix accounts for
+        // situations such as negative scaling.
+        //if !Mat3A::from_mat4(transform).determinant().is_sign_positive() {
+        //    tangent.w *= -1.0;
+        //}
+    }
+}
+
+/// Scales each vertex's joint weights so they sum to 1.0, as [`skin_model`] assumes. Vertices
+/// whose weights are all zero are left untouched.
+pub fn normalize_joint_weights(mesh: &mut Mesh) {
+    for weights in mesh_joint_weights_mut(mesh) {
+        let sum = weights.iter().sum::<f32>();
+        if sum != 0.0 {
+            *weights = weights.map(|w| w / sum);
         }
     }
+}
 
-    Some(new_mesh)
+/// Min and max corners of the skinned mesh in world space, computed without cloning the mesh or
+/// storing the transformed positions. Returns `None` if the joints aren't available or the mesh
+/// is empty.
+pub fn skinned_mesh_aabb(
+    mesh: &Mesh,
+    skinned_mesh: &SkinnedMesh,
+    joint_query: &Query<&GlobalTransform>,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+) -> Option<(Vec3, Vec3)> {
+    let joints = skinned_mesh_joints(skinned_mesh, inverse_bindposes, joint_query)?;
+    mesh_positions(mesh)
+        .zip(mesh_joint_indices(mesh))
+        .zip(mesh_joint_weights(mesh))
+        .map(|((pos, indices), weights)| {
+            skin_model(&joints, indices, &weights).transform_point3(*pos)
+        })
+        .fold(None, |aabb, p| match aabb {
+            Some((min, max)) => Some((p.min(min), p.max(max))),
+            None => Some((p, p)),
+        })
+}
+
+/// Same as [`mesh_with_skinned_transform`] but with the joint attributes removed, giving a static
+/// snapshot of the current pose. Returns `None` if the joints aren't available.
+pub fn bake_skinned_pose(
+    mesh: &Mesh,
+    skinned_mesh: &SkinnedMesh,
+    joint_query: &Query<&GlobalTransform>,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+) -> Option<Mesh> {
+    let joints = skinned_mesh_joints(skinned_mesh, inverse_bindposes, joint_query)?;
+    let mut mesh = mesh.clone();
+    skin_mesh_in_place(&mut mesh, &joints);
+    strip_skinning(&mut mesh);
+    Some(mesh)
+}
+
+/// Removes the joint index and weight attributes, if present.
+pub fn strip_skinning(mesh: &mut Mesh) {
+    mesh.remove_attribute(Mesh::ATTRIBUTE_JOINT_INDEX);
+    mesh.remove_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT);
 }
 
 #[derive(Error, Debug)]
 pub enum MeshAppendError {
     #[error("Attribute {0:?} in destination mesh not found in source mesh.")]
     AttributeNotFound(MeshVertexAttributeId),
+    #[error("Destination mesh topology {dest:?} does not match source mesh topology {src:?}.")]
+    TopologyMismatch {
+        dest: PrimitiveTopology,
+        src: PrimitiveTopology,
+    },
 }
 
 pub fn mesh_append(dest_mesh: &mut Mesh, src_mesh: &Mesh) -> Result<(), crate::MeshAppendError> {
+    append(dest_mesh, src_mesh, false)
+}
+
+/// Same as [`mesh_append`] but instead of returning [`MeshAppendError::AttributeNotFound`] when
+/// the source mesh is missing an attribute, the destination is padded with a default value
+/// (`[0.0, 0.0, 1.0]` for normals and zero otherwise) for each source vertex.
+pub fn mesh_append_fill(dest_mesh: &mut Mesh, src_mesh: &Mesh) -> Result<(), MeshAppendError> {
+    append(dest_mesh, src_mesh, true)
+}
+
+fn append(
+    dest_mesh: &mut Mesh,
+    src_mesh: &Mesh,
+    fill_missing: bool,
+) -> Result<(), MeshAppendError> {
     let dest_mesh_count = dest_mesh.count_vertices();
 
-    for (attr, _) in dest_mesh.attributes() {
-        if src_mesh.attribute(attr).is_none() {
-            return Err(MeshAppendError::AttributeNotFound(attr));
+    if dest_mesh.primitive_topology() != src_mesh.primitive_topology() {
+        return Err(MeshAppendError::TopologyMismatch {
+            dest: dest_mesh.primitive_topology(),
+            src: src_mesh.primitive_topology(),
+        });
+    }
+
+    if !fill_missing {
+        for (attr, _) in dest_mesh.attributes() {
+            if src_mesh.attribute(attr).is_none() {
+                return Err(MeshAppendError::AttributeNotFound(attr));
+            }
         }
     }
 
-    let src_indices = src_mesh.indices().unwrap().iter();
+    // Non-indexed meshes are treated as if they had sequential indices. The result is only left
+    // without indices if both meshes are non-indexed.
+    if dest_mesh.indices().is_none() && src_mesh.indices().is_some() {
+        dest_mesh.insert_indices(Indices::U32((0..dest_mesh_count as u32).collect()));
+    }
+
+    let src_indices = match src_mesh.indices() {
+        Some(indices) => indices.iter().collect::<Vec<_>>(),
+        None => (0..src_mesh.count_vertices()).collect(),
+    };
+
+    // Upgrade to U32 indices if the combined mesh has too many vertices to be addressed by U16.
+    if dest_mesh_count + src_mesh.count_vertices() > u16::MAX as usize {
+        convert_indices_u32(dest_mesh);
+    }
 
-    match dest_mesh.indices_mut().unwrap() {
-        bevy::render::mesh::Indices::U16(dv) => {
+    match dest_mesh.indices_mut() {
+        Some(bevy::render::mesh::Indices::U16(dv)) => {
             for sv in src_indices {
                 dv.push(sv as u16 + dest_mesh_count as u16)
             }
         }
-        bevy::render::mesh::Indices::U32(dv) => {
+        Some(bevy::render::mesh::Indices::U32(dv)) => {
             for sv in src_indices {
                 dv.push(sv as u32 + dest_mesh_count as u32)
             }
         }
+        None => (),
     }
 
+    let src_mesh_count = src_mesh.count_vertices();
+
     for (attr, vals) in dest_mesh.attributes_mut() {
+        if src_mesh.attribute(attr).is_none() {
+            extend_with_default(vals, src_mesh_count, attr == Mesh::ATTRIBUTE_NORMAL.id);
+            continue;
+        }
         match vals {
             VertexAttributeValues::Float32(v) => {
                 if let Some(VertexAttributeValues::Float32(s)) = src_mesh.attribute(attr) {
@@ -432,6 +785,210 @@ pub fn mesh_append(dest_mesh: &mut Mesh, src_mesh: &Mesh) -> Result<(), crate::M
     Ok(())
 }
 
+/// Reserves capacity for at least `additional` more values in `values`.
+fn reserve_attribute_values(values: &mut VertexAttributeValues, additional: usize) {
+    match values {
+        VertexAttributeValues::Float32(v) => v.reserve(additional),
+        VertexAttributeValues::Sint32(v) => v.reserve(additional),
+        VertexAttributeValues::Uint32(v) => v.reserve(additional),
+        VertexAttributeValues::Float32x2(v) => v.reserve(additional),
+        VertexAttributeValues::Sint32x2(v) => v.reserve(additional),
+        VertexAttributeValues::Uint32x2(v) => v.reserve(additional),
+        VertexAttributeValues::Float32x3(v) => v.reserve(additional),
+        VertexAttributeValues::Sint32x3(v) => v.reserve(additional),
+        VertexAttributeValues::Uint32x3(v) => v.reserve(additional),
+        VertexAttributeValues::Float32x4(v) => v.reserve(additional),
+        VertexAttributeValues::Sint32x4(v) => v.reserve(additional),
+        VertexAttributeValues::Uint32x4(v) => v.reserve(additional),
+        VertexAttributeValues::Sint16x2(v) => v.reserve(additional),
+        VertexAttributeValues::Snorm16x2(v) => v.reserve(additional),
+        VertexAttributeValues::Uint16x2(v) => v.reserve(additional),
+        VertexAttributeValues::Unorm16x2(v) => v.reserve(additional),
+        VertexAttributeValues::Sint16x4(v) => v.reserve(additional),
+        VertexAttributeValues::Snorm16x4(v) => v.reserve(additional),
+        VertexAttributeValues::Uint16x4(v) => v.reserve(additional),
+        VertexAttributeValues::Unorm16x4(v) => v.reserve(additional),
+        VertexAttributeValues::Sint8x2(v) => v.reserve(additional),
+        VertexAttributeValues::Snorm8x2(v) => v.reserve(additional),
+        VertexAttributeValues::Uint8x2(v) => v.reserve(additional),
+        VertexAttributeValues::Unorm8x2(v) => v.reserve(additional),
+        VertexAttributeValues::Sint8x4(v) => v.reserve(additional),
+        VertexAttributeValues::Snorm8x4(v) => v.reserve(additional),
+        VertexAttributeValues::Uint8x4(v) => v.reserve(additional),
+        VertexAttributeValues::Unorm8x4(v) => v.reserve(additional),
+    }
+}
+
+/// Appends `count` default values to `values`, using `[0.0, 0.0, 1.0]` for `Float32x3` when
+/// `is_normal` is set.
+fn extend_with_default(values: &mut VertexAttributeValues, count: usize, is_normal: bool) {
+    fn fill<T: Copy + Default>(v: &mut Vec<T>, count: usize) {
+        v.resize(v.len() + count, T::default());
+    }
+
+    match values {
+        VertexAttributeValues::Float32x3(v) if is_normal => {
+            v.resize(v.len() + count, [0.0, 0.0, 1.0]);
+        }
+        VertexAttributeValues::Float32(v) => fill(v, count),
+        VertexAttributeValues::Sint32(v) => fill(v, count),
+        VertexAttributeValues::Uint32(v) => fill(v, count),
+        VertexAttributeValues::Float32x2(v) => fill(v, count),
+        VertexAttributeValues::Sint32x2(v) => fill(v, count),
+        VertexAttributeValues::Uint32x2(v) => fill(v, count),
+        VertexAttributeValues::Float32x3(v) => fill(v, count),
+        VertexAttributeValues::Sint32x3(v) => fill(v, count),
+        VertexAttributeValues::Uint32x3(v) => fill(v, count),
+        VertexAttributeValues::Float32x4(v) => fill(v, count),
+        VertexAttributeValues::Sint32x4(v) => fill(v, count),
+        VertexAttributeValues::Uint32x4(v) => fill(v, count),
+        VertexAttributeValues::Sint16x2(v) => fill(v, count),
+        VertexAttributeValues::Snorm16x2(v) => fill(v, count),
+        VertexAttributeValues::Uint16x2(v) => fill(v, count),
+        VertexAttributeValues::Unorm16x2(v) => fill(v, count),
+        VertexAttributeValues::Sint16x4(v) => fill(v, count),
+        VertexAttributeValues::Snorm16x4(v) => fill(v, count),
+        VertexAttributeValues::Uint16x4(v) => fill(v, count),
+        VertexAttributeValues::Unorm16x4(v) => fill(v, count),
+        VertexAttributeValues::Sint8x2(v) => fill(v, count),
+        VertexAttributeValues::Snorm8x2(v) => fill(v, count),
+        VertexAttributeValues::Uint8x2(v) => fill(v, count),
+        VertexAttributeValues::Unorm8x2(v) => fill(v, count),
+        VertexAttributeValues::Sint8x4(v) => fill(v, count),
+        VertexAttributeValues::Snorm8x4(v) => fill(v, count),
+        VertexAttributeValues::Uint8x4(v) => fill(v, count),
+        VertexAttributeValues::Unorm8x4(v) => fill(v, count),
+    }
+}
+
+/// Combines `meshes` into a single mesh with [`mesh_append`], starting from a clone of the first
+/// one. Returns [`mesh_empty_default`] if `meshes` is empty.
+pub fn mesh_concat(meshes: &[&Mesh]) -> Result<Mesh, MeshAppendError> {
+    let Some((first, rest)) = meshes.split_first() else {
+        return Ok(mesh_empty_default());
+    };
+    let mut combined_mesh = (*first).clone();
+    for mesh in rest {
+        mesh_append(&mut combined_mesh, mesh)?;
+    }
+    Ok(combined_mesh)
+}
+
+/// Same as [`mesh_concat`] but also returns the range of the index buffer each source mesh
+/// occupies in the combined mesh, for use as draw ranges or with [`submesh_range`] after dividing
+/// by 3. Non-indexed meshes are measured in vertices, matching their implicit sequential indices.
+pub fn mesh_concat_tracked(meshes: &[&Mesh]) -> Result<(Mesh, Vec<Range<usize>>), MeshAppendError> {
+    fn index_count(mesh: &Mesh) -> usize {
+        mesh.indices()
+            .map_or_else(|| mesh.count_vertices(), |indices| indices.len())
+    }
+
+    let Some((first, rest)) = meshes.split_first() else {
+        return Ok((mesh_empty_default(), Vec::new()));
+    };
+    let mut combined_mesh = (*first).clone();
+    let mut ranges = Vec::with_capacity(meshes.len());
+    ranges.push(0..index_count(&combined_mesh));
+    for mesh in rest {
+        let start = index_count(&combined_mesh);
+        mesh_append(&mut combined_mesh, mesh)?;
+        ranges.push(start..index_count(&combined_mesh));
+    }
+    Ok((combined_mesh, ranges))
+}
+
+/// Same as [`mesh_concat`] but bakes each mesh's transform before appending it.
+pub fn mesh_concat_with_transforms(meshes: &[(&Mesh, Transform)]) -> Result<Mesh, MeshAppendError> {
+    let Some(((first, first_transform), rest)) = meshes.split_first() else {
+        return Ok(mesh_empty_default());
+    };
+    let mut combined_mesh = (*first).clone();
+    transform_mesh_in_place(&mut combined_mesh, first_transform);
+    for (mesh, transform) in rest {
+        let mut mesh = (*mesh).clone();
+        transform_mesh_in_place(&mut mesh, transform);
+        mesh_append(&mut combined_mesh, &mesh)?;
+    }
+    Ok(combined_mesh)
+}
+
+/// Combines `count` copies of `mesh` into one, each translated by `offset` from the previous one,
+/// like an array modifier. The first copy is left in place. Returns an empty mesh with the same
+/// attributes if `count` is 0.
+pub fn array_mesh(mesh: &Mesh, count: usize, offset: Vec3) -> Mesh {
+    array_mesh_with_transform(mesh, count, &Transform::from_translation(offset))
+}
+
+/// Same as [`array_mesh`] but applies `step` once more to each copy, so copy `i` is transformed by
+/// `step` `i` times. A step that both rotates and translates gives a spiral staircase.
+pub fn array_mesh_with_transform(mesh: &Mesh, count: usize, step: &Transform) -> Mesh {
+    if count == 0 {
+        return empty_like(mesh);
+    }
+    let step = step.compute_matrix();
+    let mut mat = Mat4::IDENTITY;
+    let mut combined_mesh = mesh.clone();
+    for _ in 1..count {
+        mat = step * mat;
+        // Copies of the same mesh always have matching attributes and topology
+        let _ = mesh_append(&mut combined_mesh, &mesh_with_matrix(mesh, &mat));
+    }
+    combined_mesh
+}
+
+/// Places `count` copies of `mesh` evenly around a circle of `radius` about `axis` through the
+/// origin and combines them into one mesh. The first copy is moved along X (or Z when `axis` is
+/// close to X, matching [`project_uvs_planar`]) and each copy is rotated with its position, so
+/// the side of `mesh` facing that direction always faces outward. Returns an empty mesh with the
+/// same attributes if `count` is 0.
+pub fn radial_array(mesh: &Mesh, count: usize, axis: Vec3, radius: f32) -> Mesh {
+    let axis = axis.normalize_or_zero();
+    let (outward, _) = planar_uv_axes(axis);
+    let offset = Mat4::from_translation(outward * radius);
+
+    let mut combined_mesh = empty_like(mesh);
+    for i in 0..count {
+        let angle = std::f32::consts::TAU * i as f32 / count as f32;
+        let mat = Mat4::from_quat(Quat::from_axis_angle(axis, angle)) * offset;
+        let copy = mesh_with_matrix(mesh, &mat);
+        if i == 0 {
+            combined_mesh = copy;
+        } else {
+            // Copies of the same mesh always have matching attributes and topology
+            let _ = mesh_append(&mut combined_mesh, &copy);
+        }
+    }
+    combined_mesh
+}
+
+/// Bakes each of `transforms` into a copy of `mesh` and combines them into a single mesh, for
+/// drawing many static props in one draw call. Capacity for every copy is reserved up front.
+/// Returns an empty mesh with the same attributes if `transforms` is empty.
+pub fn scatter_mesh(mesh: &Mesh, transforms: &[Transform]) -> Result<Mesh, MeshAppendError> {
+    let Some((first, rest)) = transforms.split_first() else {
+        return Ok(empty_like(mesh));
+    };
+    let mut combined_mesh = mesh_with_transform(mesh, first);
+
+    let vertex_count = mesh.count_vertices();
+    if vertex_count * transforms.len() > u16::MAX as usize {
+        convert_indices_u32(&mut combined_mesh);
+    }
+    for (_, vals) in combined_mesh.attributes_mut() {
+        reserve_attribute_values(vals, vertex_count * rest.len());
+    }
+    match combined_mesh.indices_mut() {
+        Some(Indices::U16(v)) => v.reserve(v.len() * rest.len()),
+        Some(Indices::U32(v)) => v.reserve(v.len() * rest.len()),
+        None => (),
+    }
+
+    for transform in rest {
+        mesh_append(&mut combined_mesh, &mesh_with_transform(mesh, transform))?;
+    }
+    Ok(combined_mesh)
+}
+
 pub fn mesh_empty_default() -> Mesh {
     let mut mesh = Mesh::new(
         PrimitiveTopology::TriangleList,
@@ -444,38 +1001,4331 @@ pub fn mesh_empty_default() -> Mesh {
     mesh
 }
 
-pub fn f32x3_vec3_iter_mut(v: IterMut<[f32; 3]>) -> IterMut<Vec3> {
-    unsafe { std::mem::transmute::<IterMut<[f32; 3]>, IterMut<Vec3>>(v) }
+/// Vertex indices of each triangle in a `TriangleList` mesh, read from the index buffer if there
+/// is one and treating the vertices as sequential triangles otherwise.
+fn triangle_list_indices(mesh: &Mesh) -> Vec<usize> {
+    match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..mesh_len(mesh)).collect(),
+    }
 }
 
-pub fn f32x4_vec4_iter_mut(v: IterMut<[f32; 4]>) -> IterMut<Vec4> {
-    unsafe { std::mem::transmute::<IterMut<[f32; 4]>, IterMut<Vec4>>(v) }
+/// Replaces `Mesh::ATTRIBUTE_NORMAL` with the geometric normal of each triangle. Only
+/// `TriangleList` meshes are supported, other topologies are left unchanged.
+///
+/// Vertices shared between triangles can only hold one normal, so the last triangle to reference
+/// a vertex wins. Use [`deindex_mesh`] first to get true flat shading on indexed meshes.
+pub fn compute_flat_normals(mesh: &mut Mesh) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for tri in triangle_list_indices(mesh).chunks_exact(3) {
+        let [a, b, c] = [positions[tri[0]], positions[tri[1]], positions[tri[2]]];
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        for i in tri {
+            normals[*i] = normal;
+        }
+    }
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        normals.iter().map(|n| n.to_array()).collect::<Vec<_>>(),
+    );
 }
 
-pub fn f32x3_vec3_iter(v: Iter<[f32; 3]>) -> Iter<Vec3> {
-    unsafe { std::mem::transmute::<Iter<[f32; 3]>, Iter<Vec3>>(v) }
+/// Replaces `Mesh::ATTRIBUTE_NORMAL` with smooth vertex normals. Each triangle adds its
+/// un-normalized (and so area weighted) face normal to the vertices it references, which are then
+/// normalized. Only `TriangleList` meshes are supported, other topologies are left unchanged.
+pub fn compute_smooth_normals(mesh: &mut Mesh) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for tri in triangle_list_indices(mesh).chunks_exact(3) {
+        let [a, b, c] = [positions[tri[0]], positions[tri[1]], positions[tri[2]]];
+        let normal = (b - a).cross(c - a);
+        for i in tri {
+            normals[*i] += normal;
+        }
+    }
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        normals
+            .iter()
+            .map(|n| n.normalize_or_zero().to_array())
+            .collect::<Vec<_>>(),
+    );
 }
 
-pub fn f32x4_vec4_iter(v: Iter<[f32; 4]>) -> Iter<Vec4> {
-    unsafe { std::mem::transmute::<Iter<[f32; 4]>, Iter<Vec4>>(v) }
+/// Flips stored normals that point away from the surrounding faces, keeping authored smoothing
+/// elsewhere. Each normal is compared with the area weighted normal of the triangles using its
+/// vertex and flipped when the angle between them is over 120 degrees. Only `TriangleList` meshes
+/// are supported, other topologies are left unchanged.
+pub fn fix_inverted_normals(mesh: &mut Mesh) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    let mut face_normals = vec![Vec3::ZERO; positions.len()];
+    for tri in triangle_list_indices(mesh).chunks_exact(3) {
+        let [a, b, c] = [positions[tri[0]], positions[tri[1]], positions[tri[2]]];
+        let normal = (b - a).cross(c - a);
+        for i in tri {
+            face_normals[*i] += normal;
+        }
+    }
+    for (n, face_normal) in mesh_normals_mut(mesh).zip(face_normals) {
+        if n.normalize_or_zero().dot(face_normal.normalize_or_zero()) < -0.5 {
+            *n = -*n;
+        }
+    }
 }
 
-pub fn f32x3_vec3_vec_mut(v: &mut Vec<[f32; 3]>) -> &mut Vec<Vec3> {
-    unsafe { std::mem::transmute::<&mut Vec<[f32; 3]>, &mut Vec<Vec3>>(v) }
+/// Splits vertices along edges where the angle between the two adjacent faces is above
+/// `angle_threshold_deg` and recomputes normals, so those edges render sharp while the rest stays
+/// smooth. Each vertex gets one copy per group of its faces joined by smooth edges, with the area
+/// weighted normal of that group. Edges used by one or more than two faces count as sharp, and
+/// unused vertices are dropped. Only `TriangleList` meshes are supported, other topologies are
+/// left unchanged.
+///
+/// Faces are connected by vertex index, so use [`weld_vertices`] first on meshes with split
+/// vertices.
+pub fn harden_edges(mesh: &mut Mesh, angle_threshold_deg: f32) {
+    fn find(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
+    }
+
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let indices = triangle_list_indices(mesh);
+    let positions = mesh_positions_slice(mesh);
+    let face_normals = indices
+        .chunks_exact(3)
+        .map(|tri| {
+            (positions[tri[1]] - positions[tri[0]]).cross(positions[tri[2]] - positions[tri[0]])
+        })
+        .collect::<Vec<_>>();
+    let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::new();
+    for (face, tri) in indices.chunks_exact(3).enumerate() {
+        for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+            edge_faces
+                .entry([a.min(b), a.max(b)])
+                .or_default()
+                .push(face);
+        }
+    }
+
+    // Join the corners on either side of each smooth edge, corner `i` being `indices[i]`
+    let min_cos = angle_threshold_deg.to_radians().cos();
+    let corner_of = |face: usize, vertex: usize| {
+        (face * 3..face * 3 + 3)
+            .find(|i| indices[*i] == vertex)
+            .unwrap_or(face * 3)
+    };
+    let mut parents = (0..indices.len()).collect::<Vec<_>>();
+    for (edge, faces) in &edge_faces {
+        let &[f, g] = faces.as_slice() else {
+            continue;
+        };
+        let cos = face_normals[f]
+            .normalize_or_zero()
+            .dot(face_normals[g].normalize_or_zero());
+        if cos < min_cos {
+            continue;
+        }
+        for vertex in edge {
+            let root = find(&mut parents, corner_of(f, *vertex));
+            let other = find(&mut parents, corner_of(g, *vertex));
+            parents[other] = root;
+        }
+    }
+
+    let mut groups = HashMap::new();
+    let mut kept = Vec::new();
+    let mut normals = Vec::new();
+    let mut new_indices = Vec::with_capacity(indices.len());
+    for corner in 0..indices.len() {
+        let root = find(&mut parents, corner);
+        let vertex = *groups.entry(root).or_insert_with(|| {
+            kept.push(indices[corner]);
+            normals.push(Vec3::ZERO);
+            kept.len() - 1
+        });
+        normals[vertex] += face_normals[corner / 3];
+        new_indices.push(vertex);
+    }
+
+    mesh_select_vertices(mesh, &kept);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        normals
+            .iter()
+            .map(|n| n.normalize_or_zero().to_array())
+            .collect::<Vec<_>>(),
+    );
+    if kept.len() <= u16::MAX as usize {
+        mesh.insert_indices(Indices::U16(
+            new_indices.iter().map(|i| *i as u16).collect(),
+        ));
+    } else {
+        mesh.insert_indices(Indices::U32(
+            new_indices.iter().map(|i| *i as u32).collect(),
+        ));
+    }
 }
 
-pub fn f32x4_vec4_vec_mut(v: &mut Vec<[f32; 4]>) -> &mut Vec<Vec4> {
-    unsafe { std::mem::transmute::<&mut Vec<[f32; 4]>, &mut Vec<Vec4>>(v) }
+#[derive(Error, Debug)]
+pub enum ComputeTangentsError {
+    #[error("Attribute {0:?} required to compute tangents not found in mesh.")]
+    AttributeNotFound(MeshVertexAttributeId),
+    #[error(transparent)]
+    Generate(#[from] GenerateTangentsError),
 }
 
-pub fn f32x3_vec3_vec(v: &Vec<[f32; 3]>) -> &Vec<Vec3> {
-    unsafe { std::mem::transmute::<&Vec<[f32; 3]>, &Vec<Vec3>>(v) }
+/// Generates `Mesh::ATTRIBUTE_TANGENT` (with handedness in w) from positions, normals and UV_0
+/// using the mikktspace convention.
+pub fn compute_tangents(mesh: &mut Mesh) -> Result<(), ComputeTangentsError> {
+    for attr in [
+        Mesh::ATTRIBUTE_POSITION,
+        Mesh::ATTRIBUTE_NORMAL,
+        Mesh::ATTRIBUTE_UV_0,
+    ] {
+        if mesh.attribute(attr.id).is_none() {
+            return Err(ComputeTangentsError::AttributeNotFound(attr.id));
+        }
+    }
+    mesh.generate_tangents()?;
+    Ok(())
 }
 
-pub fn f32x4_vec4_vec(v: &Vec<[f32; 4]>) -> &Vec<Vec4> {
-    unsafe { std::mem::transmute::<&Vec<[f32; 4]>, &Vec<Vec4>>(v) }
+/// Projects each tangent onto the plane perpendicular to its vertex normal (Gram-Schmidt) and
+/// renormalizes it, keeping the handedness in `w`. Useful after operations that change the normals
+/// without updating the tangents. Tangents parallel to their normal are replaced with an
+/// arbitrary perpendicular direction. Does nothing unless the mesh has both normals and tangents.
+pub fn orthonormalize_tangents(mesh: &mut Mesh) {
+    let normals = mesh_normals(mesh).copied().collect::<Vec<_>>();
+    for (tangent, normal) in mesh_tangents_mut(mesh).zip(&normals) {
+        let normal = normal.normalize_or_zero();
+        if normal == Vec3::ZERO {
+            continue;
+        }
+        let [x, y, z, w] = *tangent;
+        let xyz = Vec3::new(x, y, z);
+        let projected = xyz - normal * normal.dot(xyz);
+        let projected = projected
+            .try_normalize()
+            .unwrap_or_else(|| normal.any_orthonormal_vector());
+        *tangent = projected.extend(w).to_array();
+    }
 }
 
-pub fn f32x3_vec3_iter_mut2(v: IterMut<[f32; 3]>) -> IterMut<Vec3> {
-    unsafe { std::mem::transmute::<IterMut<[f32; 3]>, IterMut<Vec3>>(v) }
+/// Values of `values` at each of `vertices`, in order.
+fn select_attribute_values(
+    values: &VertexAttributeValues,
+    vertices: &[usize],
+) -> VertexAttributeValues {
+    fn select<T: Copy>(values: &[T], vertices: &[usize]) -> Vec<T> {
+        vertices.iter().map(|i| values[*i]).collect()
+    }
+
+    match values {
+        VertexAttributeValues::Float32(v) => VertexAttributeValues::Float32(select(v, vertices)),
+        VertexAttributeValues::Sint32(v) => VertexAttributeValues::Sint32(select(v, vertices)),
+        VertexAttributeValues::Uint32(v) => VertexAttributeValues::Uint32(select(v, vertices)),
+        VertexAttributeValues::Float32x2(v) => {
+            VertexAttributeValues::Float32x2(select(v, vertices))
+        }
+        VertexAttributeValues::Sint32x2(v) => VertexAttributeValues::Sint32x2(select(v, vertices)),
+        VertexAttributeValues::Uint32x2(v) => VertexAttributeValues::Uint32x2(select(v, vertices)),
+        VertexAttributeValues::Float32x3(v) => {
+            VertexAttributeValues::Float32x3(select(v, vertices))
+        }
+        VertexAttributeValues::Sint32x3(v) => VertexAttributeValues::Sint32x3(select(v, vertices)),
+        VertexAttributeValues::Uint32x3(v) => VertexAttributeValues::Uint32x3(select(v, vertices)),
+        VertexAttributeValues::Float32x4(v) => {
+            VertexAttributeValues::Float32x4(select(v, vertices))
+        }
+        VertexAttributeValues::Sint32x4(v) => VertexAttributeValues::Sint32x4(select(v, vertices)),
+        VertexAttributeValues::Uint32x4(v) => VertexAttributeValues::Uint32x4(select(v, vertices)),
+        VertexAttributeValues::Sint16x2(v) => VertexAttributeValues::Sint16x2(select(v, vertices)),
+        VertexAttributeValues::Snorm16x2(v) => {
+            VertexAttributeValues::Snorm16x2(select(v, vertices))
+        }
+        VertexAttributeValues::Uint16x2(v) => VertexAttributeValues::Uint16x2(select(v, vertices)),
+        VertexAttributeValues::Unorm16x2(v) => {
+            VertexAttributeValues::Unorm16x2(select(v, vertices))
+        }
+        VertexAttributeValues::Sint16x4(v) => VertexAttributeValues::Sint16x4(select(v, vertices)),
+        VertexAttributeValues::Snorm16x4(v) => {
+            VertexAttributeValues::Snorm16x4(select(v, vertices))
+        }
+        VertexAttributeValues::Uint16x4(v) => VertexAttributeValues::Uint16x4(select(v, vertices)),
+        VertexAttributeValues::Unorm16x4(v) => {
+            VertexAttributeValues::Unorm16x4(select(v, vertices))
+        }
+        VertexAttributeValues::Sint8x2(v) => VertexAttributeValues::Sint8x2(select(v, vertices)),
+        VertexAttributeValues::Snorm8x2(v) => VertexAttributeValues::Snorm8x2(select(v, vertices)),
+        VertexAttributeValues::Uint8x2(v) => VertexAttributeValues::Uint8x2(select(v, vertices)),
+        VertexAttributeValues::Unorm8x2(v) => VertexAttributeValues::Unorm8x2(select(v, vertices)),
+        VertexAttributeValues::Sint8x4(v) => VertexAttributeValues::Sint8x4(select(v, vertices)),
+        VertexAttributeValues::Snorm8x4(v) => VertexAttributeValues::Snorm8x4(select(v, vertices)),
+        VertexAttributeValues::Uint8x4(v) => VertexAttributeValues::Uint8x4(select(v, vertices)),
+        VertexAttributeValues::Unorm8x4(v) => VertexAttributeValues::Unorm8x4(select(v, vertices)),
+    }
+}
+
+/// Rebuilds every vertex attribute of `mesh` so that vertex `i` holds the values previously at
+/// `vertices[i]`. Indices are left untouched.
+fn mesh_select_vertices(mesh: &mut Mesh, vertices: &[usize]) {
+    for (_, vals) in mesh.attributes_mut() {
+        *vals = select_attribute_values(vals, vertices);
+    }
+}
+
+/// Key for `v` on an `epsilon` sized grid, or its exact bits if `epsilon` isn't positive, since
+/// dividing by zero would send every coordinate to the same saturated value.
+fn quantize(v: f32, epsilon: f32) -> i64 {
+    if epsilon > 0.0 {
+        (v / epsilon).round() as i64
+    } else {
+        v.to_bits() as i64
+    }
+}
+
+/// Merges vertices whose positions quantize to the same `epsilon` sized grid cell. Normals and
+/// UV_0 are quantized too when present, so hard edges and UV seams are kept. An `epsilon` of zero
+/// or less only merges exact duplicates. The index buffer is rewritten (or created, for
+/// non-indexed meshes) and unused vertices are dropped from every attribute.
+pub fn weld_vertices(mesh: &mut Mesh, epsilon: f32) {
+    let quantize = |v: f32| quantize(v, epsilon);
+
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    let normals = mesh_normals(mesh).copied().collect::<Vec<_>>();
+    let uvs = mesh_uvs(mesh).copied().collect::<Vec<_>>();
+
+    let mut buckets = HashMap::new();
+    let mut remap = Vec::with_capacity(positions.len());
+    let mut kept = Vec::new();
+    for (i, p) in positions.iter().enumerate() {
+        let mut key = p.to_array().map(quantize).to_vec();
+        if let Some(n) = normals.get(i) {
+            key.extend(n.to_array().map(quantize));
+        }
+        if let Some(uv) = uvs.get(i) {
+            key.extend(uv.to_array().map(quantize));
+        }
+        let new_index = *buckets.entry(key).or_insert_with(|| {
+            kept.push(i);
+            kept.len() - 1
+        });
+        remap.push(new_index);
+    }
+
+    mesh_select_vertices(mesh, &kept);
+
+    if mesh.indices().is_some() {
+        remap_indices(mesh, &remap);
+    } else {
+        mesh.insert_indices(Indices::U32(remap.iter().map(|i| *i as u32).collect()));
+    }
+}
+
+/// Expands every attribute according to the index buffer so that no vertices are shared, then
+/// removes the indices. Does nothing if the mesh has no indices.
+pub fn deindex_mesh(mesh: &mut Mesh) {
+    let Some(indices) = mesh.remove_indices() else {
+        return;
+    };
+    mesh_select_vertices(mesh, &indices.iter().collect::<Vec<_>>());
+}
+
+/// Appends the values of vertex `i` in `values` to `key`, quantizing float components to an
+/// `epsilon` sized grid so nearly identical vertices produce the same key, or comparing them
+/// exactly if `epsilon` isn't positive.
+fn push_vertex_key(values: &VertexAttributeValues, i: usize, epsilon: f32, key: &mut Vec<i64>) {
+    let quantize = |v: f32| quantize(v, epsilon);
+    match values {
+        VertexAttributeValues::Float32(v) => key.push(quantize(v[i])),
+        VertexAttributeValues::Sint32(v) => key.push(v[i] as i64),
+        VertexAttributeValues::Uint32(v) => key.push(v[i] as i64),
+        VertexAttributeValues::Float32x2(v) => key.extend(v[i].map(quantize)),
+        VertexAttributeValues::Sint32x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Uint32x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Float32x3(v) => key.extend(v[i].map(quantize)),
+        VertexAttributeValues::Sint32x3(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Uint32x3(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Float32x4(v) => key.extend(v[i].map(quantize)),
+        VertexAttributeValues::Sint32x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Uint32x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Sint16x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Snorm16x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Uint16x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Unorm16x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Sint16x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Snorm16x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Uint16x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Unorm16x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Sint8x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Snorm8x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Uint8x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Unorm8x2(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Sint8x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Snorm8x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Uint8x4(v) => key.extend(v[i].map(|x| x as i64)),
+        VertexAttributeValues::Unorm8x4(v) => key.extend(v[i].map(|x| x as i64)),
+    }
+}
+
+/// Builds an index buffer for a non-indexed mesh by collapsing vertices that are identical
+/// (within `epsilon` for float components) across every attribute. Uses `Indices::U16` when the
+/// unique vertex count allows it and `Indices::U32` otherwise. Does nothing if the mesh already
+/// has indices.
+pub fn index_mesh(mesh: &mut Mesh, epsilon: f32) {
+    if mesh.indices().is_some() {
+        return;
+    }
+
+    let mut buckets = HashMap::new();
+    let mut indices = Vec::with_capacity(mesh_len(mesh));
+    let mut kept = Vec::new();
+    for i in 0..mesh.count_vertices() {
+        let mut key = Vec::new();
+        for (_, values) in mesh.attributes() {
+            push_vertex_key(values, i, epsilon, &mut key);
+        }
+        let new_index = *buckets.entry(key).or_insert_with(|| {
+            kept.push(i);
+            kept.len() - 1
+        });
+        indices.push(new_index);
+    }
+
+    mesh_select_vertices(mesh, &kept);
+
+    if kept.len() <= u16::MAX as usize {
+        mesh.insert_indices(Indices::U16(indices.iter().map(|i| *i as u16).collect()));
+    } else {
+        mesh.insert_indices(Indices::U32(indices.iter().map(|i| *i as u32).collect()));
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Expected {expected} corner UVs but found {found}.")]
+pub struct CornerUvCountMismatchError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+/// Duplicates vertices whose triangles need different UVs, so each UV island gets its own
+/// vertices and [`compute_smooth_normals`] doesn't average across seams. A vertex can only hold
+/// one UV_0 value, so the UVs each triangle needs are given per corner in `corner_uvs`, one for
+/// each index in triangle order (or each vertex of a non-indexed mesh), as when importing formats
+/// that index UVs separately from positions.
+///
+/// Corners of a vertex that agree on their UV keep sharing it. The first UV a vertex is used with
+/// stays on the original and every other UV gets a copy appended with the same values for all
+/// other attributes. UV_0 is then written from `corner_uvs`, keeping the existing values of
+/// unused vertices. Returns an error, leaving the mesh unchanged, if `corner_uvs` doesn't have
+/// one UV per corner. Does nothing for topologies other than `TriangleList`.
+pub fn split_vertices_at_uv_seams(
+    mesh: &mut Mesh,
+    corner_uvs: &[Vec2],
+) -> Result<(), CornerUvCountMismatchError> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return Ok(());
+    }
+    let mut indices = triangle_list_indices(mesh);
+    if corner_uvs.len() != indices.len() {
+        return Err(CornerUvCountMismatchError {
+            expected: indices.len(),
+            found: corner_uvs.len(),
+        });
+    }
+    let vertex_count = mesh.count_vertices();
+    let mut uvs = mesh_uvs_slice(mesh).to_vec();
+    uvs.resize(vertex_count, Vec2::ZERO);
+
+    // UVs each vertex is used with so far, along with the vertex holding each of them
+    let mut vertex_uvs = vec![Vec::<(Vec2, usize)>::new(); vertex_count];
+    let mut copies = Vec::new();
+    for (index, uv) in indices.iter_mut().zip(corner_uvs) {
+        let used = &mut vertex_uvs[*index];
+        if let Some((_, vertex)) = used.iter().find(|(used_uv, _)| used_uv == uv) {
+            *index = *vertex;
+            continue;
+        }
+        let vertex = if used.is_empty() {
+            uvs[*index] = *uv;
+            *index
+        } else {
+            copies.push(*index);
+            uvs.push(*uv);
+            vertex_count + copies.len() - 1
+        };
+        used.push((*uv, vertex));
+        *index = vertex;
+    }
+
+    if !copies.is_empty() {
+        let order = (0..vertex_count).chain(copies).collect::<Vec<_>>();
+        mesh_select_vertices(mesh, &order);
+    }
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_UV_0,
+        uvs.iter().map(|uv| uv.to_array()).collect::<Vec<_>>(),
+    );
+    match mesh.indices() {
+        Some(Indices::U16(_)) if uvs.len() <= u16::MAX as usize => {
+            mesh.insert_indices(Indices::U16(indices.iter().map(|i| *i as u16).collect()))
+        }
+        Some(_) => mesh.insert_indices(Indices::U32(indices.iter().map(|i| *i as u32).collect())),
+        // Every corner already has its own vertex
+        None => (),
+    }
+    Ok(())
+}
+
+/// Iterates the positions of each triangle, resolving the index buffer if there is one. Yields
+/// nothing for topologies other than `TriangleList`.
+pub fn mesh_triangles(mesh: &Mesh) -> impl Iterator<Item = [Vec3; 3]> + '_ {
+    let positions = mesh_positions_slice(mesh);
+    let indices = if mesh.primitive_topology() == PrimitiveTopology::TriangleList {
+        triangle_list_indices(mesh)
+    } else {
+        Vec::new()
+    };
+    (0..indices.len() / 3).map(move |t| {
+        [
+            positions[indices[t * 3]],
+            positions[indices[t * 3 + 1]],
+            positions[indices[t * 3 + 2]],
+        ]
+    })
+}
+
+/// Average of all vertex positions, or `Vec3::ZERO` for an empty mesh.
+pub fn mesh_centroid(mesh: &Mesh) -> Vec3 {
+    let len = mesh_len(mesh);
+    if len == 0 {
+        return Vec3::ZERO;
+    }
+    mesh_positions(mesh).sum::<Vec3>() / len as f32
+}
+
+/// Centroid of the surface, weighting each triangle's midpoint by its area. Returns `Vec3::ZERO`
+/// if the mesh has no area.
+pub fn mesh_surface_centroid(mesh: &Mesh) -> Vec3 {
+    let mut weighted_sum = Vec3::ZERO;
+    let mut total_area = 0.0;
+    for [a, b, c] in mesh_triangles(mesh) {
+        let area = 0.5 * (b - a).cross(c - a).length();
+        weighted_sum += (a + b + c) / 3.0 * area;
+        total_area += area;
+    }
+    if total_area == 0.0 {
+        return Vec3::ZERO;
+    }
+    weighted_sum / total_area
+}
+
+/// Total area of all triangles. Returns 0.0 for topologies other than `TriangleList`.
+pub fn mesh_surface_area(mesh: &Mesh) -> f32 {
+    mesh_triangles(mesh)
+        .map(|[a, b, c]| 0.5 * (b - a).cross(c - a).length())
+        .sum()
+}
+
+/// Signed volume enclosed by the mesh, summed from the tetrahedra each triangle forms with the
+/// origin. Only meaningful for closed (watertight) meshes. A negative result means the triangles
+/// are wound inside out.
+pub fn mesh_volume(mesh: &Mesh) -> f32 {
+    mesh_triangles(mesh)
+        .map(|[a, b, c]| a.dot(b.cross(c)) / 6.0)
+        .sum()
+}
+
+/// Rigid body properties returned by [`mass_properties`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    pub mass: f32,
+    pub center_of_mass: Vec3,
+    /// Inertia tensor about the center of mass, in the mesh's local axes.
+    pub inertia: Mat3,
+}
+
+/// Mass, center of mass and inertia tensor of the solid enclosed by the mesh with a uniform
+/// `density`, integrated over the tetrahedra each triangle forms with the origin. Like
+/// [`mesh_volume`] this requires a watertight mesh with consistent outward winding, see
+/// [`is_watertight`].
+pub fn mass_properties(mesh: &Mesh, density: f32) -> MassProperties {
+    // Covariance of the tetrahedron (0, x, y, z) with unit determinant
+    let canonical = Mat3::from_cols_array(&[2.0, 1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0, 2.0]) / 120.0;
+
+    let mut volume = 0.0;
+    let mut weighted_center = Vec3::ZERO;
+    let mut covariance = Mat3::ZERO;
+    for [a, b, c] in mesh_triangles(mesh) {
+        let tetrahedron = Mat3::from_cols(a, b, c);
+        let det = tetrahedron.determinant();
+        volume += det / 6.0;
+        weighted_center += det / 6.0 * (a + b + c) / 4.0;
+        covariance += tetrahedron * canonical * tetrahedron.transpose() * det;
+    }
+
+    let center_of_mass = if volume != 0.0 {
+        weighted_center / volume
+    } else {
+        Vec3::ZERO
+    };
+    // Move the covariance to the center of mass (parallel axis theorem) and apply density
+    let com = center_of_mass;
+    let covariance =
+        (covariance - Mat3::from_cols(com * com.x, com * com.y, com * com.z) * volume) * density;
+    let trace = covariance.x_axis.x + covariance.y_axis.y + covariance.z_axis.z;
+    MassProperties {
+        mass: volume * density,
+        center_of_mass,
+        inertia: Mat3::from_diagonal(Vec3::splat(trace)) - covariance,
+    }
+}
+
+/// Min and max corners of the positions, or `None` for an empty mesh.
+pub fn mesh_aabb(mesh: &Mesh) -> Option<(Vec3, Vec3)> {
+    let mut positions = mesh_positions(mesh);
+    let first = *positions.next()?;
+    Some(positions.fold((first, first), |(min, max), p| (min.min(*p), max.max(*p))))
+}
+
+/// Sphere centered on the AABB that contains every position. Returns a zero radius sphere at the
+/// origin for an empty mesh.
+pub fn mesh_bounding_sphere(mesh: &Mesh) -> (Vec3, f32) {
+    let Some((min, max)) = mesh_aabb(mesh) else {
+        return (Vec3::ZERO, 0.0);
+    };
+    let center = (min + max) * 0.5;
+    let radius_squared = mesh_positions(mesh)
+        .map(|p| p.distance_squared(center))
+        .fold(0.0, f32::max);
+    (center, radius_squared.sqrt())
+}
+
+/// Appends a vertex `t` of the way from vertex `a` to vertex `b` to `values`. Float and normalized
+/// integer formats are interpolated, other integer formats (such as joint indices) can't be
+/// and take the value of the nearer vertex, `a` at the midpoint.
+fn push_vertex_lerp(values: &mut VertexAttributeValues, a: usize, b: usize, t: f32) {
+    fn lerp<const N: usize>(x: [f32; N], y: [f32; N], t: f32) -> [f32; N] {
+        std::array::from_fn(|i| x[i] + (y[i] - x[i]) * t)
+    }
+    fn lerp_norm<T, const N: usize>(x: [T; N], y: [T; N], t: f32) -> [T; N]
+    where
+        T: Copy + Into<i64> + TryFrom<i64>,
+    {
+        std::array::from_fn(|i| {
+            let (from, to) = (x[i].into() as f32, y[i].into() as f32);
+            T::try_from((from + (to - from) * t).round() as i64).unwrap_or(x[i])
+        })
+    }
+
+    let nearest = if t <= 0.5 { a } else { b };
+    match values {
+        VertexAttributeValues::Float32(v) => v.push(v[a] + (v[b] - v[a]) * t),
+        VertexAttributeValues::Sint32(v) => v.push(v[nearest]),
+        VertexAttributeValues::Uint32(v) => v.push(v[nearest]),
+        VertexAttributeValues::Float32x2(v) => v.push(lerp(v[a], v[b], t)),
+        VertexAttributeValues::Sint32x2(v) => v.push(v[nearest]),
+        VertexAttributeValues::Uint32x2(v) => v.push(v[nearest]),
+        VertexAttributeValues::Float32x3(v) => v.push(lerp(v[a], v[b], t)),
+        VertexAttributeValues::Sint32x3(v) => v.push(v[nearest]),
+        VertexAttributeValues::Uint32x3(v) => v.push(v[nearest]),
+        VertexAttributeValues::Float32x4(v) => v.push(lerp(v[a], v[b], t)),
+        VertexAttributeValues::Sint32x4(v) => v.push(v[nearest]),
+        VertexAttributeValues::Uint32x4(v) => v.push(v[nearest]),
+        VertexAttributeValues::Sint16x2(v) => v.push(v[nearest]),
+        VertexAttributeValues::Snorm16x2(v) => v.push(lerp_norm(v[a], v[b], t)),
+        VertexAttributeValues::Uint16x2(v) => v.push(v[nearest]),
+        VertexAttributeValues::Unorm16x2(v) => v.push(lerp_norm(v[a], v[b], t)),
+        VertexAttributeValues::Sint16x4(v) => v.push(v[nearest]),
+        VertexAttributeValues::Snorm16x4(v) => v.push(lerp_norm(v[a], v[b], t)),
+        VertexAttributeValues::Uint16x4(v) => v.push(v[nearest]),
+        VertexAttributeValues::Unorm16x4(v) => v.push(lerp_norm(v[a], v[b], t)),
+        VertexAttributeValues::Sint8x2(v) => v.push(v[nearest]),
+        VertexAttributeValues::Snorm8x2(v) => v.push(lerp_norm(v[a], v[b], t)),
+        VertexAttributeValues::Uint8x2(v) => v.push(v[nearest]),
+        VertexAttributeValues::Unorm8x2(v) => v.push(lerp_norm(v[a], v[b], t)),
+        VertexAttributeValues::Sint8x4(v) => v.push(v[nearest]),
+        VertexAttributeValues::Snorm8x4(v) => v.push(lerp_norm(v[a], v[b], t)),
+        VertexAttributeValues::Uint8x4(v) => v.push(v[nearest]),
+        VertexAttributeValues::Unorm8x4(v) => v.push(lerp_norm(v[a], v[b], t)),
+    }
+}
+
+/// Splits every triangle into four, `iterations` times, by inserting a vertex at the midpoint of
+/// each edge. Vertex attributes are interpolated linearly, except for integer formats that aren't
+/// normalized (such as joint indices) which are copied from one end of the edge. Normals are
+/// re-normalized. Midpoints are shared between adjacent triangles so closed meshes stay closed.
+/// Only `TriangleList` meshes are supported, other topologies are left unchanged.
+pub fn subdivide(mesh: &mut Mesh, iterations: usize) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let use_u16 = matches!(mesh.indices(), Some(Indices::U16(_)));
+
+    for _ in 0..iterations {
+        let indices = triangle_list_indices(mesh);
+        let mut vertex_count = mesh.count_vertices();
+        let mut midpoints = HashMap::new();
+        let mut new_indices = Vec::with_capacity(indices.len() * 4);
+
+        let mut midpoint = |mesh: &mut Mesh, a: usize, b: usize| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                for (_, values) in mesh.attributes_mut() {
+                    push_vertex_lerp(values, a, b, 0.5);
+                }
+                vertex_count += 1;
+                vertex_count - 1
+            })
+        };
+
+        for tri in indices.chunks_exact(3) {
+            let [a, b, c] = [tri[0], tri[1], tri[2]];
+            let ab = midpoint(mesh, a, b);
+            let bc = midpoint(mesh, b, c);
+            let ca = midpoint(mesh, c, a);
+            new_indices.extend([a, ab, ca, ab, b, bc, ca, bc, c, ab, bc, ca]);
+        }
+
+        if use_u16 && vertex_count <= u16::MAX as usize {
+            mesh.insert_indices(Indices::U16(
+                new_indices.iter().map(|i| *i as u16).collect(),
+            ));
+        } else {
+            mesh.insert_indices(Indices::U32(
+                new_indices.iter().map(|i| *i as u32).collect(),
+            ));
+        }
+    }
+
+    for n in mesh_normals_mut(mesh) {
+        *n = n.normalize_or_zero();
+    }
+}
+
+/// Keeps the part of a `TriangleList` mesh on the side of the plane through `point` that `normal`
+/// points to. Triangles crossing the plane are cut, with every attribute interpolated at the new
+/// vertices along the cut and normals re-normalized. Triangles fully behind the plane are dropped
+/// along with vertices no longer used. The cut leaves the mesh open, see [`boundary_edges`]. Other
+/// topologies are returned unchanged.
+pub fn clip_by_plane(mesh: &Mesh, point: Vec3, normal: Vec3) -> Mesh {
+    let mut clipped = mesh.clone();
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return clipped;
+    }
+    let normal = normal.normalize_or_zero();
+    let distances = mesh_positions(mesh)
+        .map(|p| normal.dot(*p - point))
+        .collect::<Vec<_>>();
+    let original_count = distances.len();
+
+    let mut vertex_count = original_count;
+    let mut cut_vertices = HashMap::new();
+    let mut cut = |mesh: &mut Mesh, a: usize, b: usize| {
+        // Interpolate from the lower index so both triangles sharing the edge get the same vertex
+        let (a, b) = (a.min(b), a.max(b));
+        *cut_vertices.entry((a, b)).or_insert_with(|| {
+            let t = distances[a] / (distances[a] - distances[b]);
+            for (_, values) in mesh.attributes_mut() {
+                push_vertex_lerp(values, a, b, t);
+            }
+            vertex_count += 1;
+            vertex_count - 1
+        })
+    };
+
+    let mut new_indices = Vec::new();
+    for tri in triangle_list_indices(mesh).chunks_exact(3) {
+        // Sutherland-Hodgman against a single plane, keeping the triangle's winding
+        let mut polygon = Vec::with_capacity(4);
+        for (i, a) in tri.iter().enumerate() {
+            let b = tri[(i + 1) % 3];
+            let (a_inside, b_inside) = (distances[*a] >= 0.0, distances[b] >= 0.0);
+            if a_inside {
+                polygon.push(*a);
+            }
+            if a_inside != b_inside {
+                polygon.push(cut(&mut clipped, *a, b));
+            }
+        }
+        for i in 1..polygon.len().saturating_sub(1) {
+            new_indices.extend([polygon[0], polygon[i], polygon[i + 1]]);
+        }
+    }
+
+    if let Some(VertexAttributeValues::Float32x3(normals)) =
+        clipped.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+    {
+        for n in &mut normals[original_count..] {
+            *n = Vec3::from(*n).normalize_or_zero().to_array();
+        }
+    }
+    if matches!(mesh.indices(), Some(Indices::U16(_))) && vertex_count <= u16::MAX as usize {
+        clipped.insert_indices(Indices::U16(
+            new_indices.iter().map(|i| *i as u16).collect(),
+        ));
+    } else {
+        clipped.insert_indices(Indices::U32(
+            new_indices.iter().map(|i| *i as u32).collect(),
+        ));
+    }
+    compact_vertices(&mut clipped);
+    clipped
+}
+
+/// Point of a mesh's bounds used by [`recenter_mesh`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    /// Center of the AABB.
+    Center,
+    /// Center of the bottom (min y) face of the AABB, for placing objects on the ground.
+    BottomCenter,
+    /// Min corner of the AABB.
+    Min,
+}
+
+/// Translates the positions so that `anchor` lands at the origin. Returns the offset that was
+/// added to every position, or `None` if the mesh is empty.
+pub fn recenter_mesh(mesh: &mut Mesh, anchor: Anchor) -> Option<Vec3> {
+    let (min, max) = mesh_aabb(mesh)?;
+    let center = (min + max) * 0.5;
+    let offset = -match anchor {
+        Anchor::Center => center,
+        Anchor::BottomCenter => Vec3::new(center.x, min.y, center.z),
+        Anchor::Min => min,
+    };
+    for p in mesh_positions_mut(mesh) {
+        *p += offset;
+    }
+    Some(offset)
+}
+
+/// Uniformly scales the positions about the origin so the largest AABB dimension equals
+/// `target_size`, and returns the scale factor applied. Use [`recenter_mesh`] first to scale
+/// about the mesh's own bounds instead. Empty or zero sized meshes are left unchanged and return
+/// 1.0.
+pub fn normalize_scale(mesh: &mut Mesh, target_size: f32) -> f32 {
+    let Some((min, max)) = mesh_aabb(mesh) else {
+        return 1.0;
+    };
+    let size = (max - min).max_element();
+    if size <= 0.0 {
+        return 1.0;
+    }
+    let scale = target_size / size;
+    for p in mesh_positions_mut(mesh) {
+        *p *= scale;
+    }
+    scale
+}
+
+/// Writes the positions, normals, UV_0 and triangles of `mesh` as Wavefront OBJ. Normals and UVs
+/// are omitted from the output if the mesh doesn't have them, and faces are only written for
+/// `TriangleList` meshes.
+pub fn write_obj(mesh: &Mesh, writer: &mut impl Write) -> io::Result<()> {
+    for p in mesh_positions(mesh) {
+        writeln!(writer, "v {} {} {}", p.x, p.y, p.z)?;
+    }
+    for n in mesh_normals(mesh) {
+        writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+    }
+    // OBJ texture coordinates have their origin at the bottom left
+    for uv in mesh_uvs(mesh) {
+        writeln!(writer, "vt {} {}", uv.x, 1.0 - uv.y)?;
+    }
+
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return Ok(());
+    }
+    let has_normals = mesh_normals(mesh).len() > 0;
+    let has_uvs = mesh_uvs(mesh).len() > 0;
+    for tri in triangle_list_indices(mesh).chunks_exact(3) {
+        write!(writer, "f")?;
+        for i in tri {
+            // OBJ indices are 1-based
+            let i = i + 1;
+            match (has_uvs, has_normals) {
+                (true, true) => write!(writer, " {i}/{i}/{i}")?,
+                (true, false) => write!(writer, " {i}/{i}")?,
+                (false, true) => write!(writer, " {i}//{i}")?,
+                (false, false) => write!(writer, " {i}")?,
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ObjError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Could not parse OBJ line {0}.")]
+    InvalidLine(usize),
+    #[error("OBJ line {0} references an element that does not exist.")]
+    IndexOutOfRange(usize),
+}
+
+/// Reads a Wavefront OBJ into a `TriangleList` mesh with positions, normals and UV_0 (when the
+/// file has them) and a `U32` index buffer. Each unique `v/vt/vn` combination becomes one vertex,
+/// and faces with more than three vertices are triangulated as a fan. Elements other than `v`,
+/// `vt`, `vn` and `f` are ignored.
+pub fn read_obj(reader: &mut impl BufRead) -> Result<Mesh, ObjError> {
+    fn parse_floats<const N: usize>(
+        values: &mut std::str::SplitWhitespace,
+        line: usize,
+    ) -> Result<[f32; N], ObjError> {
+        let values = values
+            .take(N)
+            .map(|v| v.parse::<f32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ObjError::InvalidLine(line))?;
+        values.try_into().map_err(|_| ObjError::InvalidLine(line))
+    }
+
+    // Resolves a 1-based (or negative, relative to the end) OBJ index.
+    fn resolve(index: &str, len: usize, line: usize) -> Result<usize, ObjError> {
+        let index = index
+            .parse::<isize>()
+            .map_err(|_| ObjError::InvalidLine(line))?;
+        let resolved = if index < 0 {
+            len as isize + index
+        } else {
+            index - 1
+        };
+        if resolved < 0 || resolved as usize >= len {
+            return Err(ObjError::IndexOutOfRange(line));
+        }
+        Ok(resolved as usize)
+    }
+
+    let mut obj_positions = Vec::new();
+    let mut obj_normals = Vec::new();
+    let mut obj_uvs = Vec::new();
+
+    let mut vertices = HashMap::new();
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut has_normals = false;
+    let mut has_uvs = false;
+    let mut indices = Vec::new();
+
+    for (line_index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_number = line_index + 1;
+        let mut values = line.split_whitespace();
+        match values.next() {
+            Some("v") => obj_positions.push(parse_floats::<3>(&mut values, line_number)?),
+            Some("vn") => obj_normals.push(parse_floats::<3>(&mut values, line_number)?),
+            // OBJ texture coordinates have their origin at the bottom left
+            Some("vt") => {
+                let [u] = parse_floats::<1>(&mut values, line_number)?;
+                // v is optional and defaults to 0
+                let v = match values.next() {
+                    Some(v) => v.parse().map_err(|_| ObjError::InvalidLine(line_number))?,
+                    None => 0.0,
+                };
+                obj_uvs.push([u, 1.0 - v]);
+            }
+            Some("f") => {
+                let mut face = Vec::new();
+                for vertex in values {
+                    let mut parts = vertex.split('/');
+                    let position = parts.next().ok_or(ObjError::InvalidLine(line_number))?;
+                    let position = resolve(position, obj_positions.len(), line_number)?;
+                    let uv = match parts.next() {
+                        Some("") | None => None,
+                        Some(uv) => Some(resolve(uv, obj_uvs.len(), line_number)?),
+                    };
+                    let normal = match parts.next() {
+                        Some("") | None => None,
+                        Some(normal) => Some(resolve(normal, obj_normals.len(), line_number)?),
+                    };
+
+                    let index = *vertices.entry((position, uv, normal)).or_insert_with(|| {
+                        positions.push(obj_positions[position]);
+                        uvs.push(uv.map_or([0.0; 2], |uv| obj_uvs[uv]));
+                        normals.push(normal.map_or([0.0; 3], |normal| obj_normals[normal]));
+                        positions.len() as u32 - 1
+                    });
+                    has_uvs |= uv.is_some();
+                    has_normals |= normal.is_some();
+                    face.push(index);
+                }
+                if face.len() < 3 {
+                    return Err(ObjError::InvalidLine(line_number));
+                }
+                for i in 1..face.len() - 1 {
+                    indices.extend([face[0], face[i], face[i + 1]]);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    if has_normals {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    }
+    if has_uvs {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    }
+    mesh.insert_indices(Indices::U32(indices));
+    Ok(mesh)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// Index of the triangle that was hit, as yielded by [`mesh_triangles`].
+    pub triangle_index: usize,
+    /// Distance along the ray direction, in units of its length.
+    pub distance: f32,
+    /// Weights of the triangle's three vertices at the hit point.
+    pub barycentric: Vec3,
+    pub position: Vec3,
+}
+
+/// Möller–Trumbore intersection of a ray with a single triangle. Returns the distance along the
+/// ray and the barycentric weights of the hit.
+fn ray_triangle_intersection(origin: Vec3, dir: Vec3, [a, b, c]: [Vec3; 3]) -> Option<(f32, Vec3)> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let pvec = dir.cross(edge2);
+    let det = edge1.dot(pvec);
+    // `det` scales with the triangle's area and the ray's length, so the parallel test has to be
+    // relative to them or small triangles would never be hit.
+    if det.abs() <= f32::EPSILON * edge1.length() * edge2.length() * dir.length() {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let tvec = origin - a;
+    let u = tvec.dot(pvec) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let qvec = tvec.cross(edge1);
+    let v = dir.dot(qvec) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = edge2.dot(qvec) * inv_det;
+    (t > 0.0).then_some((t, Vec3::new(1.0 - u - v, u, v)))
+}
+
+/// Finds the nearest triangle hit by the ray starting at `origin` going along `dir`. Both sides
+/// of each triangle are tested.
+pub fn raycast_mesh(mesh: &Mesh, origin: Vec3, dir: Vec3) -> Option<RayHit> {
+    let mut nearest: Option<RayHit> = None;
+    for (triangle_index, tri) in mesh_triangles(mesh).enumerate() {
+        let Some((distance, barycentric)) = ray_triangle_intersection(origin, dir, tri) else {
+            continue;
+        };
+        if !nearest.is_some_and(|hit| hit.distance <= distance) {
+            nearest = Some(RayHit {
+                triangle_index,
+                distance,
+                barycentric,
+                position: tri[0] * barycentric.x + tri[1] * barycentric.y + tri[2] * barycentric.z,
+            });
+        }
+    }
+    nearest
+}
+
+/// Nearest point on a mesh's surface, returned by [`closest_point`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfacePoint {
+    /// Index of the triangle the point lies on, as yielded by [`mesh_triangles`].
+    pub triangle_index: usize,
+    /// Distance from the query point.
+    pub distance: f32,
+    /// Weights of the triangle's three vertices at the point, for [`sample_attribute_at`].
+    pub barycentric: Vec3,
+    pub position: Vec3,
+}
+
+/// Barycentric weights of the point on triangle `[a, b, c]` closest to `p`, from Real-Time
+/// Collision Detection by Christer Ericson.
+fn closest_point_on_triangle(p: Vec3, [a, b, c]: [Vec3; 3]) -> Vec3 {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return Vec3::X;
+    }
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return Vec3::Y;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return Vec3::new(1.0 - v, v, 0.0);
+    }
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return Vec3::Z;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return Vec3::new(1.0 - w, 0.0, w);
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return Vec3::new(0.0, 1.0 - w, w);
+    }
+    let denom = 1.0 / (va + vb + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    Vec3::new(1.0 - v - w, v, w)
+}
+
+/// Keeps whichever of `nearest` and the closest point on `tri` to `query` is nearer.
+fn nearer_surface_point(
+    nearest: &mut Option<SurfacePoint>,
+    query: Vec3,
+    triangle_index: usize,
+    tri: [Vec3; 3],
+) {
+    let barycentric = closest_point_on_triangle(query, tri);
+    let position = tri[0] * barycentric.x + tri[1] * barycentric.y + tri[2] * barycentric.z;
+    let distance = position.distance(query);
+    if !nearest.is_some_and(|point| point.distance <= distance) {
+        *nearest = Some(SurfacePoint {
+            triangle_index,
+            distance,
+            barycentric,
+            position,
+        });
+    }
+}
+
+/// Finds the point on the mesh's surface nearest to `query` by testing every triangle, or `None`
+/// if the mesh has no triangles. Use [`MeshBvh::closest_point`] for repeated queries.
+pub fn closest_point(mesh: &Mesh, query: Vec3) -> Option<SurfacePoint> {
+    let mut nearest = None;
+    for (triangle_index, tri) in mesh_triangles(mesh).enumerate() {
+        nearer_surface_point(&mut nearest, query, triangle_index, tri);
+    }
+    nearest
+}
+
+/// Generalized winding number of `triangles` around `point`: about 1 inside a closed, outward
+/// wound surface and 0 outside, summed from the solid angle of each triangle.
+fn winding_number(triangles: &[[Vec3; 3]], point: Vec3) -> f32 {
+    let solid_angle = triangles
+        .iter()
+        .map(|tri| {
+            // Van Oosterom and Strackee
+            let [a, b, c] = tri.map(|v| v - point);
+            let (la, lb, lc) = (a.length(), b.length(), c.length());
+            let numerator = a.dot(b.cross(c));
+            let denominator = la * lb * lc + a.dot(b) * lc + a.dot(c) * lb + b.dot(c) * la;
+            2.0 * numerator.atan2(denominator)
+        })
+        .sum::<f32>();
+    solid_angle / (4.0 * std::f32::consts::PI)
+}
+
+/// Signed distance from `point` to the mesh's surface, negative inside. The distance comes from
+/// [`closest_point`] and the sign from the winding number of the triangles around `point`, so
+/// signs are only meaningful for closed meshes with outward winding. Returns `f32::INFINITY` if
+/// the mesh has no triangles.
+pub fn sample_sdf(mesh: &Mesh, point: Vec3) -> f32 {
+    let triangles = mesh_triangles(mesh).collect::<Vec<_>>();
+    let Some(nearest) = closest_point(mesh, point) else {
+        return f32::INFINITY;
+    };
+    if winding_number(&triangles, point) > 0.5 {
+        -nearest.distance
+    } else {
+        nearest.distance
+    }
+}
+
+/// Samples [`sample_sdf`] at `resolution` points along each axis spread evenly over the box
+/// `bounds` (min, max), both ends included. Values are ordered with X varying fastest, then Y,
+/// then Z. Uses a [`MeshBvh`] for the distances, but the sign still visits every triangle per
+/// sample. Returns nothing if the sample count doesn't fit in a `usize`.
+pub fn sample_sdf_grid(mesh: &Mesh, bounds: (Vec3, Vec3), resolution: UVec3) -> Vec<f32> {
+    let (min, max) = bounds;
+    let step = (max - min) / (resolution.as_vec3() - 1.0).max(Vec3::ONE);
+    let Some(sample_count) = (resolution.x as usize)
+        .checked_mul(resolution.y as usize)
+        .and_then(|count| count.checked_mul(resolution.z as usize))
+    else {
+        return Vec::new();
+    };
+    let triangles = mesh_triangles(mesh).collect::<Vec<_>>();
+    let bvh = MeshBvh::build(mesh);
+
+    let mut values = Vec::with_capacity(sample_count);
+    for z in 0..resolution.z {
+        for y in 0..resolution.y {
+            for x in 0..resolution.x {
+                let point = min + UVec3::new(x, y, z).as_vec3() * step;
+                let value = match bvh.closest_point(point) {
+                    Some(nearest) if winding_number(&triangles, point) > 0.5 => -nearest.distance,
+                    Some(nearest) => nearest.distance,
+                    None => f32::INFINITY,
+                };
+                values.push(value);
+            }
+        }
+    }
+    values
+}
+
+/// Which cells [`voxelize`] marks as occupied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoxelizeMode {
+    /// Cells the triangles pass through.
+    Surface,
+    /// Surface cells plus the cells inside the mesh.
+    Solid,
+}
+
+/// Occupancy grid returned by [`voxelize`]. Cell `(x, y, z)` spans from
+/// `origin + UVec3::new(x, y, z).as_vec3() * cell_size` to one `cell_size` further on each axis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoxelGrid {
+    pub origin: Vec3,
+    pub dims: UVec3,
+    pub cell_size: f32,
+    /// One entry per cell, with X varying fastest, then Y, then Z.
+    pub occupied: Vec<bool>,
+}
+
+impl VoxelGrid {
+    /// Index into `occupied` of cell `cell`.
+    pub fn index(&self, cell: UVec3) -> usize {
+        cell.x as usize
+            + (cell.y as usize + cell.z as usize * self.dims.y as usize) * self.dims.x as usize
+    }
+
+    /// Returns false for cells outside the grid.
+    pub fn is_occupied(&self, cell: UVec3) -> bool {
+        cell.cmplt(self.dims).all() && self.occupied[self.index(cell)]
+    }
+
+    pub fn cell_center(&self, cell: UVec3) -> Vec3 {
+        self.origin + (cell.as_vec3() + 0.5) * self.cell_size
+    }
+}
+
+/// Separating axis test between a triangle and an axis aligned box, from Akenine-Möller's
+/// triangle-box overlap test.
+fn triangle_overlaps_box(tri: [Vec3; 3], center: Vec3, half_size: Vec3) -> bool {
+    let [a, b, c] = tri.map(|v| v - center);
+    let edges = [b - a, c - b, a - c];
+    let box_axes = [Vec3::X, Vec3::Y, Vec3::Z];
+    let axes = box_axes
+        .into_iter()
+        .chain([edges[0].cross(edges[1])])
+        .chain(
+            box_axes
+                .iter()
+                .flat_map(|axis| edges.map(|edge| axis.cross(edge))),
+        );
+    for axis in axes {
+        if axis.length_squared() < f32::EPSILON * f32::EPSILON {
+            continue;
+        }
+        let projections = [a.dot(axis), b.dot(axis), c.dot(axis)];
+        let min = projections[0].min(projections[1]).min(projections[2]);
+        let max = projections[0].max(projections[1]).max(projections[2]);
+        let radius = half_size.dot(axis.abs());
+        if min > radius || max < -radius {
+            return false;
+        }
+    }
+    true
+}
+
+/// Marks the cells of a grid with `cell_size` cells covering the mesh's AABB that the mesh
+/// occupies. [`VoxelizeMode::Solid`] also fills the interior by casting a ray along X through the
+/// center of each row of cells and counting crossings, so it needs a closed mesh. Returns an empty
+/// grid if the mesh has no positions, or if `cell_size` isn't a positive finite value or is so
+/// small the cell count doesn't fit in a `usize`.
+pub fn voxelize(mesh: &Mesh, cell_size: f32, mode: VoxelizeMode) -> VoxelGrid {
+    let empty = VoxelGrid {
+        origin: Vec3::ZERO,
+        dims: UVec3::ZERO,
+        cell_size,
+        occupied: Vec::new(),
+    };
+    if !(cell_size > 0.0 && cell_size.is_finite()) {
+        return empty;
+    }
+    let Some((min, max)) = mesh_aabb(mesh) else {
+        return empty;
+    };
+    let extent = ((max - min) / cell_size).ceil();
+    if extent.cmpgt(Vec3::splat(u32::MAX as f32)).any() {
+        return empty;
+    }
+    let dims = extent.as_uvec3().max(UVec3::ONE);
+    let Some(cell_count) = (dims.x as usize)
+        .checked_mul(dims.y as usize)
+        .and_then(|count| count.checked_mul(dims.z as usize))
+    else {
+        return empty;
+    };
+    let mut grid = VoxelGrid {
+        origin: min,
+        dims,
+        cell_size,
+        occupied: vec![false; cell_count],
+    };
+
+    let cell_of = |p: Vec3| ((p - min) / cell_size).floor().as_uvec3().min(dims - 1);
+    let half_size = Vec3::splat(cell_size * 0.5);
+    let triangles = mesh_triangles(mesh).collect::<Vec<_>>();
+    for tri in &triangles {
+        let tri_min = cell_of(tri[0].min(tri[1]).min(tri[2]));
+        let tri_max = cell_of(tri[0].max(tri[1]).max(tri[2]));
+        for z in tri_min.z..=tri_max.z {
+            for y in tri_min.y..=tri_max.y {
+                for x in tri_min.x..=tri_max.x {
+                    let cell = UVec3::new(x, y, z);
+                    if triangle_overlaps_box(*tri, grid.cell_center(cell), half_size) {
+                        let index = grid.index(cell);
+                        grid.occupied[index] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    if mode == VoxelizeMode::Solid {
+        for z in 0..dims.z {
+            for y in 0..dims.y {
+                let start = grid.cell_center(UVec3::new(0, y, z)) - Vec3::X * cell_size;
+                let mut crossings = triangles
+                    .iter()
+                    .filter_map(|tri| ray_triangle_intersection(start, Vec3::X, *tri))
+                    .map(|(distance, _)| distance)
+                    .collect::<Vec<_>>();
+                crossings.sort_by(f32::total_cmp);
+                for x in 0..dims.x {
+                    let distance = (x + 1) as f32 * cell_size;
+                    let inside = crossings.partition_point(|t| *t < distance) % 2 == 1;
+                    if inside {
+                        let index = grid.index(UVec3::new(x, y, z));
+                        grid.occupied[index] = true;
+                    }
+                }
+            }
+        }
+    }
+    grid
+}
+
+/// Extracts the surface where `field` crosses `iso` as an indexed `TriangleList` mesh, the
+/// inverse of [`sample_sdf_grid`]. `field` holds `dims` samples along each axis spaced
+/// `cell_size` apart from `origin`, with X varying fastest, then Y, then Z. Values below `iso`
+/// count as inside, so triangles face towards increasing values and normals come from central
+/// differences of the field. Returns a mesh without triangles if `field` doesn't hold one value
+/// per sample.
+pub fn marching_cubes(field: &[f32], dims: UVec3, origin: Vec3, cell_size: f32, iso: f32) -> Mesh {
+    // Corner i of a cell is offset along X by bit 0 of i, along Y by bit 1 and along Z by bit 2.
+    // Faces list their corners in order around the face.
+    const FACES: [[usize; 4]; 6] = [
+        [0, 2, 6, 4],
+        [1, 3, 7, 5],
+        [0, 1, 5, 4],
+        [2, 3, 7, 6],
+        [0, 1, 3, 2],
+        [4, 5, 7, 6],
+    ];
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut indices = Vec::new();
+
+    let sample_count = (dims.x as usize)
+        .checked_mul(dims.y as usize)
+        .and_then(|count| count.checked_mul(dims.z as usize));
+    if sample_count == Some(field.len()) && dims.cmpge(UVec3::splat(2)).all() {
+        let index = |p: UVec3| {
+            p.x as usize + (p.y as usize + p.z as usize * dims.y as usize) * dims.x as usize
+        };
+        let gradient = |p: UVec3| {
+            let mut gradient = Vec3::ZERO;
+            for axis in 0..3 {
+                let (mut lo, mut hi) = (p, p);
+                lo[axis] = lo[axis].saturating_sub(1);
+                hi[axis] = (hi[axis] + 1).min(dims[axis] - 1);
+                gradient[axis] = (field[index(hi)] - field[index(lo)])
+                    / ((hi[axis] - lo[axis]) as f32 * cell_size);
+            }
+            gradient
+        };
+        // Position and normal where the surface crosses the edge from `corner` along `axis`
+        let edge_point = |corner: UVec3, axis: usize| {
+            let mut other = corner;
+            other[axis] += 1;
+            let t = (iso - field[index(corner)]) / (field[index(other)] - field[index(corner)]);
+            (
+                origin + corner.as_vec3().lerp(other.as_vec3(), t) * cell_size,
+                gradient(corner).lerp(gradient(other), t),
+            )
+        };
+
+        // Keyed by the lower corner and axis of the edge so neighbouring cells share vertices
+        let mut edge_vertices = HashMap::new();
+        for z in 0..dims.z - 1 {
+            for y in 0..dims.y - 1 {
+                for x in 0..dims.x - 1 {
+                    let cell = UVec3::new(x, y, z);
+                    let corners: [UVec3; 8] = std::array::from_fn(|i| {
+                        cell + UVec3::new(i as u32 & 1, (i as u32 >> 1) & 1, i as u32 >> 2)
+                    });
+                    let inside = corners.map(|c| field[index(c)] < iso);
+                    if inside.iter().all(|i| *i) || !inside.iter().any(|i| *i) {
+                        continue;
+                    }
+
+                    // Each edge crossing the surface lies on two faces, and each face links the
+                    // crossed edges on it in pairs, so following the links traces closed loops.
+                    // Faces with all four edges crossed cut off their inside corners, which only
+                    // depends on the face so the neighbouring cell picks the same pairs.
+                    let edge =
+                        |a: usize, b: usize| a.min(b) * 3 + (a ^ b).trailing_zeros() as usize;
+                    let mut links = [const { Vec::new() }; 24];
+                    let mut link = |a: usize, b: usize| {
+                        links[a].push(b);
+                        links[b].push(a);
+                    };
+                    for face in FACES {
+                        let crossed = (0..4)
+                            .filter(|i| inside[face[*i]] != inside[face[(i + 1) % 4]])
+                            .map(|i| edge(face[i], face[(i + 1) % 4]))
+                            .collect::<Vec<_>>();
+                        if crossed.len() == 2 {
+                            link(crossed[0], crossed[1]);
+                        } else if crossed.len() == 4 {
+                            for i in (0..4).filter(|i| inside[face[*i]]) {
+                                let [prev, next] = [face[(i + 3) % 4], face[(i + 1) % 4]];
+                                link(edge(prev, face[i]), edge(face[i], next));
+                            }
+                        }
+                    }
+
+                    let mut visited = [false; 24];
+                    for start in 0..24 {
+                        if visited[start] || links[start].is_empty() {
+                            continue;
+                        }
+                        let mut polygon = vec![start];
+                        let (mut prev, mut current) = (start, links[start][0]);
+                        while current != start {
+                            polygon.push(current);
+                            let next = if links[current][0] == prev {
+                                links[current][1]
+                            } else {
+                                links[current][0]
+                            };
+                            (prev, current) = (current, next);
+                        }
+                        let points = polygon
+                            .iter()
+                            .map(|e| {
+                                visited[*e] = true;
+                                (corners[e / 3], e % 3)
+                            })
+                            .collect::<Vec<_>>();
+
+                        // Wind the loop so it faces along the gradient
+                        let (mut area, mut direction) = (Vec3::ZERO, Vec3::ZERO);
+                        for (i, (corner, axis)) in points.iter().enumerate() {
+                            let (a, gradient) = edge_point(*corner, *axis);
+                            let (b, _) = edge_point(
+                                points[(i + 1) % points.len()].0,
+                                points[(i + 1) % points.len()].1,
+                            );
+                            area += a.cross(b);
+                            direction += gradient;
+                        }
+                        let mut vertices = points
+                            .iter()
+                            .map(|(corner, axis)| {
+                                *edge_vertices
+                                    .entry((index(*corner), *axis))
+                                    .or_insert_with(|| {
+                                        let (position, normal) = edge_point(*corner, *axis);
+                                        positions.push(position.to_array());
+                                        normals.push(normal.normalize_or_zero().to_array());
+                                        positions.len() as u32 - 1
+                                    })
+                            })
+                            .collect::<Vec<_>>();
+                        if area.dot(direction) < 0.0 {
+                            vertices.reverse();
+                        }
+                        for i in 1..vertices.len() - 1 {
+                            indices.extend([vertices[0], vertices[i], vertices[i + 1]]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+/// Node of a [`MeshBvh`], either a leaf holding a range of triangles or an inner node with two
+/// children.
+#[derive(Clone, Debug)]
+struct BvhNode {
+    min: Vec3,
+    max: Vec3,
+    /// Range into `MeshBvh::triangle_order` for leaves.
+    triangles: Range<usize>,
+    children: Option<[usize; 2]>,
+}
+
+/// Bounding volume hierarchy over the triangles of a mesh, for fast repeated ray casts. It holds a
+/// copy of the triangle positions, so rebuild it after editing the mesh.
+#[derive(Clone, Debug, Default)]
+pub struct MeshBvh {
+    nodes: Vec<BvhNode>,
+    triangles: Vec<[Vec3; 3]>,
+    /// Triangle indices, ordered so each leaf's triangles are contiguous.
+    triangle_order: Vec<usize>,
+}
+
+impl MeshBvh {
+    const LEAF_SIZE: usize = 4;
+
+    /// Builds the hierarchy over the triangles yielded by [`mesh_triangles`], splitting each node
+    /// at the median of the triangle centroids along its longest axis.
+    pub fn build(mesh: &Mesh) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::new(),
+            triangles: mesh_triangles(mesh).collect(),
+            triangle_order: Vec::new(),
+        };
+        bvh.triangle_order = (0..bvh.triangles.len()).collect();
+        if !bvh.triangles.is_empty() {
+            bvh.build_node(0..bvh.triangles.len());
+        }
+        bvh
+    }
+
+    fn build_node(&mut self, range: Range<usize>) -> usize {
+        let (mut min, mut max) = (Vec3::INFINITY, Vec3::NEG_INFINITY);
+        let (mut centroid_min, mut centroid_max) = (Vec3::INFINITY, Vec3::NEG_INFINITY);
+        for t in &self.triangle_order[range.clone()] {
+            let tri = self.triangles[*t];
+            for p in tri {
+                min = min.min(p);
+                max = max.max(p);
+            }
+            let centroid = (tri[0] + tri[1] + tri[2]) / 3.0;
+            centroid_min = centroid_min.min(centroid);
+            centroid_max = centroid_max.max(centroid);
+        }
+
+        let node = self.nodes.len();
+        self.nodes.push(BvhNode {
+            min,
+            max,
+            triangles: range.clone(),
+            children: None,
+        });
+        if range.len() <= Self::LEAF_SIZE {
+            return node;
+        }
+
+        let extent = centroid_max - centroid_min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let mid = range.len() / 2;
+        let triangles = &self.triangles;
+        self.triangle_order[range.clone()].select_nth_unstable_by(mid, |a, b| {
+            let [a, b] =
+                [*a, *b].map(|t| (triangles[t][0] + triangles[t][1] + triangles[t][2])[axis]);
+            a.total_cmp(&b)
+        });
+        let left = self.build_node(range.start..range.start + mid);
+        let right = self.build_node(range.start + mid..range.end);
+        self.nodes[node].children = Some([left, right]);
+        node
+    }
+
+    /// Same as [`raycast_mesh`] on the mesh the hierarchy was built from, but only tests the
+    /// triangles in nodes the ray passes through.
+    pub fn raycast(&self, origin: Vec3, dir: Vec3) -> Option<RayHit> {
+        let inv_dir = dir.recip();
+        let mut nearest: Option<RayHit> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            // Slab test, skipping nodes further away than the nearest hit so far
+            let t1 = (node.min - origin) * inv_dir;
+            let t2 = (node.max - origin) * inv_dir;
+            let t_min = t1.min(t2).max_element().max(0.0);
+            let t_max = t1.max(t2).min_element();
+            if t_max < t_min || nearest.is_some_and(|hit| hit.distance < t_min) {
+                continue;
+            }
+            if let Some(children) = node.children {
+                stack.extend(children);
+                continue;
+            }
+            for triangle_index in &self.triangle_order[node.triangles.clone()] {
+                let tri = self.triangles[*triangle_index];
+                let Some((distance, barycentric)) = ray_triangle_intersection(origin, dir, tri)
+                else {
+                    continue;
+                };
+                if !nearest.is_some_and(|hit| hit.distance <= distance) {
+                    nearest = Some(RayHit {
+                        triangle_index: *triangle_index,
+                        distance,
+                        barycentric,
+                        position: tri[0] * barycentric.x
+                            + tri[1] * barycentric.y
+                            + tri[2] * barycentric.z,
+                    });
+                }
+            }
+        }
+        nearest
+    }
+
+    /// Same as [`closest_point`] on the mesh the hierarchy was built from, but skips nodes further
+    /// away than the nearest point found so far.
+    pub fn closest_point(&self, query: Vec3) -> Option<SurfacePoint> {
+        let mut nearest: Option<SurfacePoint> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            let node_distance = query.clamp(node.min, node.max).distance(query);
+            if nearest.is_some_and(|point| point.distance <= node_distance) {
+                continue;
+            }
+            if let Some([left, right]) = node.children {
+                // Visit the nearer child first so the other can more often be skipped
+                let distance_to = |n: usize| {
+                    let n = &self.nodes[n];
+                    query.clamp(n.min, n.max).distance_squared(query)
+                };
+                if distance_to(left) < distance_to(right) {
+                    stack.extend([right, left]);
+                } else {
+                    stack.extend([left, right]);
+                }
+                continue;
+            }
+            for triangle_index in &self.triangle_order[node.triangles.clone()] {
+                nearer_surface_point(
+                    &mut nearest,
+                    query,
+                    *triangle_index,
+                    self.triangles[*triangle_index],
+                );
+            }
+        }
+        nearest
+    }
+}
+
+/// Ambient occlusion at each vertex: the fraction of `samples` rays cast over the hemisphere
+/// around the vertex normal that escape the mesh, from 0 for fully occluded to 1 for open. Rays
+/// are spread with a fixed cosine-weighted pattern, so results are repeatable, and tested against
+/// a [`MeshBvh`]. Uses the mesh's normals if it has them, otherwise smooth normals from
+/// [`compute_smooth_normals`].
+///
+/// Cost grows linearly with `samples` while the noise in the result only falls with its square
+/// root. Around 16 samples shows the broad shading, 64 to 256 gives smooth gradients for baking.
+pub fn bake_vertex_ao(mesh: &Mesh, samples: usize) -> Vec<f32> {
+    let mut smooth_normals = None;
+    let normals = if mesh_normals_slice(mesh).len() == mesh_len(mesh) {
+        mesh_normals_slice(mesh)
+    } else {
+        let smooth = smooth_normals.insert(mesh.clone());
+        compute_smooth_normals(smooth);
+        mesh_normals_slice(smooth)
+    };
+    let bvh = MeshBvh::build(mesh);
+    // Pushes ray origins off the surface so they don't hit their own triangles
+    let bias = mesh_aabb(mesh).map_or(0.0, |(min, max)| (max - min).length() * 1e-4);
+
+    // Cosine-weighted hemisphere around +Z, with the angle around it stepped by the golden ratio
+    let directions = (0..samples)
+        .map(|i| {
+            let u = (i as f32 + 0.5) / samples as f32;
+            let angle = std::f32::consts::TAU * (i as f32 * 0.618_034).fract();
+            let r = u.sqrt();
+            Vec3::new(r * angle.cos(), r * angle.sin(), (1.0 - u).sqrt())
+        })
+        .collect::<Vec<_>>();
+
+    mesh_positions(mesh)
+        .zip(normals)
+        .map(|(p, n)| {
+            let n = n.normalize_or_zero();
+            if samples == 0 || n == Vec3::ZERO {
+                return 1.0;
+            }
+            let (tangent, bitangent) = n.any_orthonormal_pair();
+            let origin = *p + n * bias;
+            let open = directions
+                .iter()
+                .filter(|d| {
+                    let dir = tangent * d.x + bitangent * d.y + n * d.z;
+                    bvh.raycast(origin, dir).is_none()
+                })
+                .count();
+            open as f32 / samples as f32
+        })
+        .collect()
+}
+
+/// Multiplies the RGB of `Mesh::ATTRIBUTE_COLOR` by [`bake_vertex_ao`], starting from white if
+/// the mesh has no colors. Alpha is left unchanged.
+pub fn bake_ao_to_colors(mesh: &mut Mesh, samples: usize) {
+    let ao = bake_vertex_ao(mesh, samples);
+    let mut colors = mesh_colors(mesh);
+    if colors.len() != ao.len() {
+        colors = vec![Vec4::ONE; ao.len()];
+    }
+    for (color, ao) in colors.iter_mut().zip(ao) {
+        *color = (color.xyz() * ao).extend(color.w);
+    }
+    mesh_set_colors(mesh, &colors);
+}
+
+/// KD-tree over the vertex positions of a mesh for nearest vertex and radius queries, such as
+/// snapping a brush to the closest vertex. Queries return indices into the mesh's vertices. It
+/// holds a copy of the positions, so rebuild it after editing the mesh.
+#[derive(Clone, Debug, Default)]
+pub struct VertexKdTree {
+    /// Vertex indices arranged as an implicit tree: the median of each range is the node
+    /// splitting it, with the lower half to its left and upper half to its right.
+    vertices: Vec<usize>,
+    positions: Vec<Vec3>,
+}
+
+impl VertexKdTree {
+    /// Builds the tree over the mesh's positions, splitting at the median along X, Y and Z in
+    /// turn.
+    pub fn build(mesh: &Mesh) -> Self {
+        let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+        let mut vertices = (0..positions.len()).collect::<Vec<_>>();
+        Self::build_range(&positions, &mut vertices, 0);
+        Self {
+            vertices,
+            positions,
+        }
+    }
+
+    fn build_range(positions: &[Vec3], vertices: &mut [usize], depth: usize) {
+        if vertices.len() <= 1 {
+            return;
+        }
+        let axis = depth % 3;
+        let mid = vertices.len() / 2;
+        vertices.select_nth_unstable_by(mid, |a, b| {
+            positions[*a][axis].total_cmp(&positions[*b][axis])
+        });
+        let (lower, upper) = vertices.split_at_mut(mid);
+        Self::build_range(positions, lower, depth + 1);
+        Self::build_range(positions, &mut upper[1..], depth + 1);
+    }
+
+    /// Index of the vertex nearest to `point`, or `None` if the mesh has no vertices.
+    pub fn nearest(&self, point: Vec3) -> Option<usize> {
+        let mut nearest = None;
+        self.nearest_in(point, 0..self.vertices.len(), 0, &mut nearest);
+        nearest.map(|(vertex, _)| vertex)
+    }
+
+    fn nearest_in(
+        &self,
+        point: Vec3,
+        range: Range<usize>,
+        depth: usize,
+        nearest: &mut Option<(usize, f32)>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let mid = range.start + range.len() / 2;
+        let vertex = self.vertices[mid];
+        let distance_squared = self.positions[vertex].distance_squared(point);
+        if !nearest.is_some_and(|(_, nearest)| nearest <= distance_squared) {
+            *nearest = Some((vertex, distance_squared));
+        }
+
+        let offset = point[depth % 3] - self.positions[vertex][depth % 3];
+        let (near, far) = if offset < 0.0 {
+            (range.start..mid, mid + 1..range.end)
+        } else {
+            (mid + 1..range.end, range.start..mid)
+        };
+        self.nearest_in(point, near, depth + 1, nearest);
+        // The far side can only be closer if the splitting plane is
+        if !nearest.is_some_and(|(_, nearest)| nearest <= offset * offset) {
+            self.nearest_in(point, far, depth + 1, nearest);
+        }
+    }
+
+    /// Indices of every vertex within `radius` of `point`, in no particular order.
+    pub fn within_radius(&self, point: Vec3, radius: f32) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut stack = vec![(0..self.vertices.len(), 0)];
+        while let Some((range, depth)) = stack.pop() {
+            if range.is_empty() {
+                continue;
+            }
+            let mid = range.start + range.len() / 2;
+            let vertex = self.vertices[mid];
+            if self.positions[vertex].distance_squared(point) <= radius * radius {
+                found.push(vertex);
+            }
+            let offset = point[depth % 3] - self.positions[vertex][depth % 3];
+            if offset - radius <= 0.0 {
+                stack.push((range.start..mid, depth + 1));
+            }
+            if offset + radius >= 0.0 {
+                stack.push((mid + 1..range.end, depth + 1));
+            }
+        }
+        found
+    }
+}
+
+/// Vertex indices of triangle `triangle_index` in a `TriangleList` mesh, or `None` if there is no
+/// such triangle.
+fn triangle_vertex_indices(mesh: &Mesh, triangle_index: usize) -> Option<[usize; 3]> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let first = triangle_index * 3;
+    let tri = match mesh.indices() {
+        Some(Indices::U16(v)) => v
+            .get(first..first + 3)?
+            .iter()
+            .map(|i| *i as usize)
+            .collect(),
+        Some(Indices::U32(v)) => v
+            .get(first..first + 3)?
+            .iter()
+            .map(|i| *i as usize)
+            .collect(),
+        None => (first..first + 3).collect::<Vec<_>>(),
+    };
+    let len = mesh_len(mesh);
+    tri.iter()
+        .all(|i| *i < len)
+        .then(|| [tri[0], tri[1], tri[2]])
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InterpolatedVertex {
+    pub position: Vec3,
+    /// `None` if the mesh has no normals.
+    pub normal: Option<Vec3>,
+    /// `None` if the mesh has no UV_0.
+    pub uv: Option<Vec2>,
+}
+
+/// Blends the vertices of triangle `triangle_index` by the `barycentric` weights, such as those
+/// from a [`RayHit`]. The normal is re-normalized. Returns `None` if there is no such triangle.
+pub fn sample_attribute_at(
+    mesh: &Mesh,
+    triangle_index: usize,
+    barycentric: Vec3,
+) -> Option<InterpolatedVertex> {
+    let [a, b, c] = triangle_vertex_indices(mesh, triangle_index)?;
+    let positions = mesh_positions_slice(mesh);
+    let normals = mesh_normals_slice(mesh);
+    let uvs = mesh_uvs_slice(mesh);
+    let position =
+        positions[a] * barycentric.x + positions[b] * barycentric.y + positions[c] * barycentric.z;
+    let normal = (!normals.is_empty()).then(|| {
+        (normals[a] * barycentric.x + normals[b] * barycentric.y + normals[c] * barycentric.z)
+            .normalize_or_zero()
+    });
+    let uv = (!uvs.is_empty())
+        .then(|| uvs[a] * barycentric.x + uvs[b] * barycentric.y + uvs[c] * barycentric.z);
+    Some(InterpolatedVertex {
+        position,
+        normal,
+        uv,
+    })
+}
+
+/// Picks `n` points distributed uniformly over the surface, returned as position and normal
+/// pairs. Normals are interpolated from the vertex normals if the mesh has them, otherwise the
+/// face normal is used. Returns nothing if the mesh has no area. Requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn sample_surface_points(mesh: &Mesh, n: usize, rng: &mut impl rand::Rng) -> Vec<(Vec3, Vec3)> {
+    let triangles = mesh_triangles(mesh).collect::<Vec<_>>();
+    let mut cumulative_areas = Vec::with_capacity(triangles.len());
+    let mut total_area = 0.0;
+    for [a, b, c] in &triangles {
+        total_area += 0.5 * (*b - *a).cross(*c - *a).length();
+        cumulative_areas.push(total_area);
+    }
+    if total_area <= 0.0 {
+        return Vec::new();
+    }
+
+    let indices = triangle_list_indices(mesh);
+    let normals = mesh_normals_slice(mesh);
+
+    (0..n)
+        .map(|_| {
+            let target = rng.gen::<f32>() * total_area;
+            let t = cumulative_areas
+                .partition_point(|area| *area <= target)
+                .min(triangles.len() - 1);
+            let [a, b, c] = triangles[t];
+
+            let s = rng.gen::<f32>().sqrt();
+            let r = rng.gen::<f32>();
+            let barycentric = Vec3::new(1.0 - s, s * (1.0 - r), s * r);
+            let position = a * barycentric.x + b * barycentric.y + c * barycentric.z;
+
+            let normal = if normals.is_empty() {
+                (b - a).cross(c - a).normalize_or_zero()
+            } else {
+                (normals[indices[t * 3]] * barycentric.x
+                    + normals[indices[t * 3 + 1]] * barycentric.y
+                    + normals[indices[t * 3 + 2]] * barycentric.z)
+                    .normalize_or_zero()
+            };
+            (position, normal)
+        })
+        .collect()
+}
+
+/// Builds a mesh from the triangles of `src_mesh` described by `indices`, containing only the
+/// vertices they reference. `template` must be a copy of `src_mesh` with empty attributes, see
+/// [`empty_like`]. Keeps the index format of `src_mesh`.
+fn submesh(template: &Mesh, src_mesh: &Mesh, indices: &[usize]) -> Mesh {
+    let mut remap = HashMap::new();
+    let mut vertices = Vec::new();
+    let new_indices = indices
+        .iter()
+        .map(|i| {
+            *remap.entry(*i).or_insert_with(|| {
+                vertices.push(*i);
+                vertices.len() - 1
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut mesh = template.clone();
+    for (attr, vals) in mesh.attributes_mut() {
+        if let Some(src_vals) = src_mesh.attribute(attr) {
+            *vals = select_attribute_values(src_vals, &vertices);
+        }
+    }
+    match src_mesh.indices() {
+        Some(Indices::U16(_)) => mesh.insert_indices(Indices::U16(
+            new_indices.iter().map(|i| *i as u16).collect(),
+        )),
+        _ => mesh.insert_indices(Indices::U32(
+            new_indices.iter().map(|i| *i as u32).collect(),
+        )),
+    }
+    mesh
+}
+
+/// Copy of `mesh` with the same attributes and topology but no vertices or indices.
+fn empty_like(mesh: &Mesh) -> Mesh {
+    let mut empty = mesh.clone();
+    mesh_select_vertices(&mut empty, &[]);
+    empty.remove_indices();
+    empty
+}
+
+/// Splits a `TriangleList` mesh into one mesh per group of triangles connected through shared
+/// vertices. Triangles that only touch at coincident but separate vertices are not considered
+/// connected, use [`weld_vertices`] first if needed. Other topologies are returned as a single
+/// unchanged copy.
+pub fn split_connected_components(mesh: &Mesh) -> Vec<Mesh> {
+    fn find(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
+    }
+
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return vec![mesh.clone()];
+    }
+
+    let indices = triangle_list_indices(mesh);
+    let mut parents = (0..mesh.count_vertices()).collect::<Vec<_>>();
+    for tri in indices.chunks_exact(3) {
+        let root = find(&mut parents, tri[0]);
+        for i in &tri[1..] {
+            let other = find(&mut parents, *i);
+            parents[other] = root;
+        }
+    }
+
+    // Group triangles by root, keeping components in order of their first triangle
+    let mut components = HashMap::new();
+    let mut component_indices: Vec<Vec<usize>> = Vec::new();
+    for tri in indices.chunks_exact(3) {
+        let root = find(&mut parents, tri[0]);
+        let component = *components.entry(root).or_insert_with(|| {
+            component_indices.push(Vec::new());
+            component_indices.len() - 1
+        });
+        component_indices[component].extend(tri);
+    }
+
+    let template = empty_like(mesh);
+    component_indices
+        .iter()
+        .map(|indices| submesh(&template, mesh, indices))
+        .collect()
+}
+
+/// Splits `mesh` with [`split_connected_components`] and recenters each part on its AABB center
+/// with [`recenter_mesh`]. Returns each part with the translation that places it back where it
+/// was, e.g. to spawn it as its own entity.
+pub fn split_and_recenter(mesh: &Mesh) -> Vec<(Mesh, Vec3)> {
+    split_connected_components(mesh)
+        .into_iter()
+        .map(|mut part| {
+            let offset = recenter_mesh(&mut part, Anchor::Center).unwrap_or_default();
+            (part, -offset)
+        })
+        .collect()
+}
+
+/// Copies triangles `tri_start..tri_end` of a `TriangleList` mesh into a new mesh with only the
+/// vertices they reference, keeping every attribute. The range is clamped to the triangle count.
+/// The result is indexed even if `mesh` isn't.
+pub fn submesh_range(mesh: &Mesh, tri_start: usize, tri_end: usize) -> Mesh {
+    let indices = triangle_list_indices(mesh);
+    let end = (tri_end * 3).min(indices.len() / 3 * 3);
+    let start = (tri_start * 3).min(end);
+    submesh(&empty_like(mesh), mesh, &indices[start..end])
+}
+
+/// Drops vertices that aren't referenced by the index buffer from every attribute and rewrites
+/// the indices to match. Does nothing for non-indexed meshes or if every vertex is used.
+pub fn compact_vertices(mesh: &mut Mesh) {
+    let Some(indices) = mesh.indices() else {
+        return;
+    };
+    let mut used = vec![false; mesh.count_vertices()];
+    for i in indices.iter() {
+        used[i] = true;
+    }
+    if used.iter().all(|used| *used) {
+        return;
+    }
+
+    let mut remap = vec![0; used.len()];
+    let mut kept = Vec::new();
+    for (i, used) in used.iter().enumerate() {
+        if *used {
+            remap[i] = kept.len();
+            kept.push(i);
+        }
+    }
+
+    mesh_select_vertices(mesh, &kept);
+    remap_indices(mesh, &remap);
+}
+
+/// Replaces each index `i` with `remap[i]`, keeping the index format.
+fn remap_indices(mesh: &mut Mesh, remap: &[usize]) {
+    match mesh.indices_mut() {
+        Some(Indices::U16(indices)) => {
+            for i in indices.iter_mut() {
+                *i = remap[*i as usize] as u16;
+            }
+        }
+        Some(Indices::U32(indices)) => {
+            for i in indices.iter_mut() {
+                *i = remap[*i as usize] as u32;
+            }
+        }
+        None => (),
+    }
+}
+
+/// Keeps only the triangles of a `TriangleList` mesh for which `keep` returns true, rewriting the
+/// index buffer in its current format. Non-indexed meshes have the dropped triangles' vertices
+/// removed instead.
+fn retain_triangles(mesh: &mut Mesh, mut keep: impl FnMut([usize; 3]) -> bool) {
+    let kept = triangle_list_indices(mesh)
+        .chunks_exact(3)
+        .filter(|tri| keep([tri[0], tri[1], tri[2]]))
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    match mesh.indices() {
+        Some(Indices::U16(_)) => {
+            mesh.insert_indices(Indices::U16(kept.iter().map(|i| *i as u16).collect()))
+        }
+        Some(Indices::U32(_)) => {
+            mesh.insert_indices(Indices::U32(kept.iter().map(|i| *i as u32).collect()))
+        }
+        None => mesh_select_vertices(mesh, &kept),
+    }
+}
+
+/// Drops triangles whose area is below `epsilon` or that reference the same vertex twice. Only
+/// `TriangleList` meshes are supported, other topologies are left unchanged. Vertices are kept in
+/// indexed meshes, use [`compact_vertices`] afterwards to remove the ones no longer referenced.
+pub fn remove_degenerate_triangles(mesh: &mut Mesh, epsilon: f32) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    retain_triangles(mesh, |[a, b, c]| {
+        let area = 0.5
+            * (positions[b] - positions[a])
+                .cross(positions[c] - positions[a])
+                .length();
+        a != b && b != c && a != c && area >= epsilon
+    });
+}
+
+/// Drops triangles that use the same three vertices as an earlier triangle, keeping the first
+/// occurrence. Triangles with opposite winding only count as duplicates if `ignore_winding` is
+/// set. Only `TriangleList` meshes are supported, other topologies are left unchanged. Triangles
+/// are compared by vertex index, use [`weld_vertices`] first to also catch coincident vertices.
+pub fn remove_duplicate_triangles(mesh: &mut Mesh, ignore_winding: bool) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let mut seen = HashSet::new();
+    retain_triangles(mesh, |mut tri| {
+        if ignore_winding {
+            tri.sort_unstable();
+        } else {
+            // Rotate the smallest index to the front, which keeps the winding
+            let min = (0..3).min_by_key(|i| tri[*i]).unwrap();
+            tri.rotate_left(min);
+        }
+        seen.insert(tri)
+    });
+}
+
+/// Counts of what [`sanitize_mesh`] removed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SanitizeReport {
+    /// Vertices merged by welding or dropped as unused.
+    pub vertices_removed: usize,
+    pub degenerate_triangles_removed: usize,
+    pub duplicate_triangles_removed: usize,
+}
+
+/// Cleans up imported geometry by running [`weld_vertices`] and [`remove_degenerate_triangles`]
+/// with `epsilon`, then [`remove_duplicate_triangles`] (respecting winding) and
+/// [`compact_vertices`]. Welding goes first so triangles collapsed or duplicated by merging
+/// coincident vertices are caught by the later steps.
+pub fn sanitize_mesh(mesh: &mut Mesh, epsilon: f32) -> SanitizeReport {
+    let triangle_count = |mesh: &Mesh| triangle_list_indices(mesh).len() / 3;
+    let vertex_count = mesh_len(mesh);
+
+    weld_vertices(mesh, epsilon);
+    let welded_triangle_count = triangle_count(mesh);
+    remove_degenerate_triangles(mesh, epsilon);
+    let non_degenerate_triangle_count = triangle_count(mesh);
+    remove_duplicate_triangles(mesh, false);
+    let unique_triangle_count = triangle_count(mesh);
+    compact_vertices(mesh);
+
+    SanitizeReport {
+        vertices_removed: vertex_count - mesh_len(mesh),
+        degenerate_triangles_removed: welded_triangle_count - non_degenerate_triangle_count,
+        duplicate_triangles_removed: non_degenerate_triangle_count - unique_triangle_count,
+    }
+}
+
+/// Number of triangles using each edge of a triangle list, keyed by the sorted vertex indices.
+fn edge_use_counts(indices: &[usize]) -> HashMap<[usize; 2], usize> {
+    let mut counts = HashMap::new();
+    for tri in indices.chunks_exact(3) {
+        for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+            *counts.entry([a.min(b), a.max(b)]).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Edges of a triangle list used by exactly one triangle, in the direction that triangle winds
+/// them.
+fn boundary_half_edges(indices: &[usize]) -> Vec<[usize; 2]> {
+    let counts = edge_use_counts(indices);
+    indices
+        .chunks_exact(3)
+        .flat_map(|tri| [[tri[0], tri[1]], [tri[1], tri[2]], [tri[2], tri[0]]])
+        .filter(|[a, b]| counts[&[*a.min(b), *a.max(b)]] == 1)
+        .collect()
+}
+
+/// Vertex index pairs of the edges used by exactly one triangle, ordered as that triangle winds
+/// them. Returns nothing for closed meshes and for topologies other than `TriangleList`.
+/// Edges are compared by vertex index, so use [`weld_vertices`] first on meshes with split
+/// vertices.
+pub fn boundary_edges(mesh: &Mesh) -> Vec<[u32; 2]> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return Vec::new();
+    }
+    boundary_half_edges(&triangle_list_indices(mesh))
+        .into_iter()
+        .map(|edge| edge.map(|i| i as u32))
+        .collect()
+}
+
+/// Vertex index pairs, smallest first, of the edges used by three or more triangles. Together
+/// with [`boundary_edges`] this tells closed, open and non-manifold meshes apart. Returns nothing
+/// for topologies other than `TriangleList`.
+pub fn non_manifold_edges(mesh: &Mesh) -> Vec<[u32; 2]> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return Vec::new();
+    }
+    let mut edges = edge_use_counts(&triangle_list_indices(mesh))
+        .into_iter()
+        .filter(|(_, count)| *count > 2)
+        .map(|(edge, _)| edge.map(|i| i as u32))
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges
+}
+
+/// Returns true if the mesh has triangles and every edge is used by exactly two of them, which is
+/// what 3D printing and volume calculations need. Only `TriangleList` meshes can be watertight.
+/// Edges are compared by vertex index, so use [`weld_vertices`] first on meshes with split
+/// vertices.
+pub fn is_watertight(mesh: &Mesh) -> bool {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return false;
+    }
+    let counts = edge_use_counts(&triangle_list_indices(mesh));
+    !counts.is_empty() && counts.values().all(|count| *count == 2)
+}
+
+/// Counts returned by [`mesh_topology_report`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TopologyReport {
+    /// Vertices referenced by at least one triangle.
+    pub vertex_count: usize,
+    pub edge_count: usize,
+    pub face_count: usize,
+    /// Edges used by exactly one triangle, see [`boundary_edges`].
+    pub boundary_edge_count: usize,
+    /// Edges used by three or more triangles, see [`non_manifold_edges`].
+    pub non_manifold_edge_count: usize,
+    /// `V - E + F`, which is 2 for a closed mesh without handles and drops by 2 for each handle.
+    pub euler_characteristic: i64,
+}
+
+/// Counts the vertices, edges and faces of a `TriangleList` mesh along with its boundary and
+/// non-manifold edges. Other topologies return an empty report. Like [`is_watertight`] this
+/// relies on shared vertices, so use [`weld_vertices`] first on meshes with split vertices.
+pub fn mesh_topology_report(mesh: &Mesh) -> TopologyReport {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return TopologyReport::default();
+    }
+    let indices = triangle_list_indices(mesh);
+    let counts = edge_use_counts(&indices);
+    let vertex_count = indices.iter().collect::<HashSet<_>>().len();
+    let face_count = indices.len() / 3;
+    TopologyReport {
+        vertex_count,
+        edge_count: counts.len(),
+        face_count,
+        boundary_edge_count: counts.values().filter(|count| **count == 1).count(),
+        non_manifold_edge_count: counts.values().filter(|count| **count > 2).count(),
+        euler_characteristic: vertex_count as i64 - counts.len() as i64 + face_count as i64,
+    }
+}
+
+/// Flips triangles so neighbors sharing an edge wind it in opposite directions, spreading the
+/// orientation of the first triangle of each connected piece across its edges. Closed pieces are
+/// then flipped as a whole if their volume is negative, so they wind outward as [`mesh_volume`]
+/// and [`mass_properties`] expect. Open pieces keep the winding of their first triangle. Only
+/// `TriangleList` meshes are supported, other topologies are left unchanged.
+///
+/// Across edges used by more than two triangles every neighbor is oriented against the first
+/// triangle that reaches it, and pieces that can't be oriented, like a Möbius strip, keep
+/// whatever conflicts remain. Vertices are connected by index, so use [`weld_vertices`] first on
+/// meshes with split vertices.
+pub fn make_consistent_winding(mesh: &mut Mesh) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let mut indices = triangle_list_indices(mesh);
+    let face_count = indices.len() / 3;
+    let mut edge_faces = HashMap::<[usize; 2], Vec<usize>>::new();
+    for (face, tri) in indices.chunks_exact(3).enumerate() {
+        for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+            edge_faces
+                .entry([a.min(b), a.max(b)])
+                .or_default()
+                .push(face);
+        }
+    }
+    let has_directed_edge =
+        |tri: &[usize], a: usize, b: usize| (0..3).any(|i| tri[i] == a && tri[(i + 1) % 3] == b);
+
+    let mut visited = vec![false; face_count];
+    let mut stack = Vec::new();
+    for start in 0..face_count {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        stack.push(start);
+        let mut piece = Vec::new();
+        let mut closed = true;
+        while let Some(face) = stack.pop() {
+            piece.push(face);
+            let tri = [
+                indices[face * 3],
+                indices[face * 3 + 1],
+                indices[face * 3 + 2],
+            ];
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                let neighbors = &edge_faces[&[a.min(b), a.max(b)]];
+                closed &= neighbors.len() > 1;
+                for neighbor in neighbors {
+                    if visited[*neighbor] {
+                        continue;
+                    }
+                    visited[*neighbor] = true;
+                    // The neighbor should run along the shared edge from b to a
+                    if has_directed_edge(&indices[neighbor * 3..neighbor * 3 + 3], a, b) {
+                        indices.swap(neighbor * 3 + 1, neighbor * 3 + 2);
+                    }
+                    stack.push(*neighbor);
+                }
+            }
+        }
+
+        if closed {
+            let positions = mesh_positions_slice(mesh);
+            let volume = piece
+                .iter()
+                .map(|face| {
+                    let [a, b, c] = [0, 1, 2].map(|i| positions[indices[face * 3 + i]]);
+                    a.dot(b.cross(c))
+                })
+                .sum::<f32>();
+            if volume < 0.0 {
+                for face in piece {
+                    indices.swap(face * 3 + 1, face * 3 + 2);
+                }
+            }
+        }
+    }
+
+    match mesh.indices() {
+        Some(Indices::U16(_)) => {
+            mesh.insert_indices(Indices::U16(indices.iter().map(|i| *i as u16).collect()))
+        }
+        Some(Indices::U32(_)) => {
+            mesh.insert_indices(Indices::U32(indices.iter().map(|i| *i as u32).collect()))
+        }
+        None => mesh_select_vertices(mesh, &indices),
+    }
+}
+
+/// One side of an edge in a [`HalfEdgeMesh`], wound in the direction of the face it belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HalfEdge {
+    /// Vertex the half edge starts from.
+    pub vertex: usize,
+    /// Half edge running the other way along the same edge in the neighboring face, or `None` on
+    /// a boundary.
+    pub twin: Option<usize>,
+    /// Next half edge around the same face.
+    pub next: usize,
+    pub face: usize,
+}
+
+/// Connectivity of a `TriangleList` mesh, built once so operations can walk between neighboring
+/// vertices and faces without re-deriving adjacency from the index buffer. Face `f` is made of
+/// half edges `3 * f`, `3 * f + 1` and `3 * f + 2`.
+///
+/// Vertices are connected by index, so use [`weld_vertices`] first on meshes with split
+/// vertices. Edges used by more than two faces only get a twin for one of them.
+#[derive(Clone, Debug)]
+pub struct HalfEdgeMesh {
+    half_edges: Vec<HalfEdge>,
+    /// An outgoing half edge for each vertex, preferring one on the boundary, or `None` for
+    /// unreferenced vertices.
+    vertex_half_edges: Vec<Option<usize>>,
+    mesh: Mesh,
+}
+
+impl HalfEdgeMesh {
+    /// Builds the half edges of `mesh`, keeping a copy of its attributes for [`Self::to_mesh`].
+    /// Returns `None` for topologies other than `TriangleList`.
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let indices = triangle_list_indices(mesh);
+        let face_count = indices.len() / 3;
+
+        let mut half_edges = Vec::with_capacity(face_count * 3);
+        let mut directed = HashMap::with_capacity(face_count * 3);
+        for (face, tri) in indices.chunks_exact(3).enumerate() {
+            for corner in 0..3 {
+                let h = face * 3 + corner;
+                directed.insert([tri[corner], tri[(corner + 1) % 3]], h);
+                half_edges.push(HalfEdge {
+                    vertex: tri[corner],
+                    twin: None,
+                    next: face * 3 + (corner + 1) % 3,
+                    face,
+                });
+            }
+        }
+
+        let mut vertex_half_edges = vec![None; mesh.count_vertices()];
+        for h in 0..half_edges.len() {
+            let a = half_edges[h].vertex;
+            let b = half_edges[half_edges[h].next].vertex;
+            half_edges[h].twin = directed.get(&[b, a]).copied();
+            let outgoing = &mut vertex_half_edges[a];
+            if outgoing.is_none() || half_edges[h].twin.is_none() {
+                *outgoing = Some(h);
+            }
+        }
+
+        Some(Self {
+            half_edges,
+            vertex_half_edges,
+            mesh: mesh.clone(),
+        })
+    }
+
+    /// Converts back to a `Mesh` with the original attributes and an index buffer built from the
+    /// faces, keeping the index format of the original mesh.
+    pub fn to_mesh(&self) -> Mesh {
+        let mut mesh = self.mesh.clone();
+        let indices = self.half_edges.iter().map(|h| h.vertex);
+        match self.mesh.indices() {
+            Some(Indices::U16(_)) => {
+                mesh.insert_indices(Indices::U16(indices.map(|i| i as u16).collect()))
+            }
+            _ => mesh.insert_indices(Indices::U32(indices.map(|i| i as u32).collect())),
+        }
+        mesh
+    }
+
+    pub fn half_edges(&self) -> &[HalfEdge] {
+        &self.half_edges
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_half_edges.len()
+    }
+
+    pub fn face_count(&self) -> usize {
+        self.half_edges.len() / 3
+    }
+
+    /// Vertex half edge `h` points to.
+    pub fn destination(&self, h: usize) -> usize {
+        self.half_edges[self.half_edges[h].next].vertex
+    }
+
+    /// Half edge before `h` around its face.
+    pub fn prev(&self, h: usize) -> usize {
+        self.half_edges[self.half_edges[h].next].next
+    }
+
+    /// Returns true if `vertex` is on a boundary edge or not used by any face.
+    pub fn is_boundary_vertex(&self, vertex: usize) -> bool {
+        !self.vertex_half_edges[vertex].is_some_and(|h| {
+            self.half_edges[h].twin.is_some() && self.half_edges[self.prev(h)].twin.is_some()
+        })
+    }
+
+    /// Vertices sharing an edge with `vertex`, found by walking the faces around it.
+    pub fn vertex_neighbors(&self, vertex: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut neighbors = Vec::new();
+        if let Some(start) = self.vertex_half_edges[vertex] {
+            let mut h = start;
+            // Bounded so non-manifold fans can't loop forever
+            for _ in 0..self.half_edges.len() {
+                neighbors.push(self.destination(h));
+                let prev = self.prev(h);
+                match self.half_edges[prev].twin {
+                    Some(twin) if twin != start => h = twin,
+                    Some(_) => break,
+                    None => {
+                        neighbors.push(self.half_edges[prev].vertex);
+                        break;
+                    }
+                }
+            }
+        }
+        neighbors.into_iter()
+    }
+
+    /// Start and end vertex of each edge of `face`, in winding order.
+    pub fn face_edges(&self, face: usize) -> impl Iterator<Item = [usize; 2]> + '_ {
+        (face * 3..face * 3 + 3).map(|h| [self.half_edges[h].vertex, self.destination(h)])
+    }
+}
+
+/// Moves each vertex `lambda` of the way toward the average of its neighbors, `iterations`
+/// times, to reduce noise. Boundary vertices stay in place when `pin_boundary` is set so holes
+/// and open edges don't shrink. Normals are not updated, use [`compute_smooth_normals`]
+/// afterwards. Only `TriangleList` meshes are supported, other topologies are left unchanged.
+///
+/// Neighbors are found by vertex index, so use [`weld_vertices`] first on meshes with split
+/// vertices.
+pub fn laplacian_smooth(mesh: &mut Mesh, iterations: usize, lambda: f32, pin_boundary: bool) {
+    let Some(half_edge_mesh) = HalfEdgeMesh::from_mesh(mesh) else {
+        return;
+    };
+    let neighbors = (0..half_edge_mesh.vertex_count())
+        .map(|v| {
+            if pin_boundary && half_edge_mesh.is_boundary_vertex(v) {
+                Vec::new()
+            } else {
+                half_edge_mesh.vertex_neighbors(v).collect()
+            }
+        })
+        .collect::<Vec<Vec<_>>>();
+
+    let mut positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    for _ in 0..iterations {
+        let previous = positions.clone();
+        for (p, neighbors) in positions.iter_mut().zip(&neighbors) {
+            if neighbors.is_empty() {
+                continue;
+            }
+            let average =
+                neighbors.iter().map(|n| previous[*n]).sum::<Vec3>() / neighbors.len() as f32;
+            *p += (average - *p) * lambda;
+        }
+    }
+    for (p, smoothed) in mesh_positions_mut(mesh).zip(positions) {
+        *p = smoothed;
+    }
+}
+
+/// Signed mean curvature at each vertex from the cotangent Laplace-Beltrami operator over its
+/// one-ring, using a third of the area of each surrounding triangle as the vertex's area. Convex
+/// regions of an outward wound surface are positive, a sphere of radius `r` gives about `1 / r`.
+/// Boundary vertices, unreferenced vertices and all vertices of topologies other than
+/// `TriangleList` get 0, as the one-ring isn't closed around them.
+///
+/// Neighbors are found by vertex index, so use [`weld_vertices`] first on meshes with split
+/// vertices.
+pub fn mean_curvature(mesh: &Mesh) -> Vec<f32> {
+    let Some(half_edge_mesh) = HalfEdgeMesh::from_mesh(mesh) else {
+        return vec![0.0; mesh_len(mesh)];
+    };
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    let mut laplacian = vec![Vec3::ZERO; positions.len()];
+    let mut areas = vec![0.0; positions.len()];
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for tri in triangle_list_indices(mesh).chunks_exact(3) {
+        let normal =
+            (positions[tri[1]] - positions[tri[0]]).cross(positions[tri[2]] - positions[tri[0]]);
+        for corner in 0..3 {
+            let [k, i, j] = [0, 1, 2].map(|offset| tri[(corner + offset) % 3]);
+            // Cotangent of the angle at k, weighting the edge opposite it
+            let (to_i, to_j) = (positions[i] - positions[k], positions[j] - positions[k]);
+            let sin = to_i.cross(to_j).length();
+            if sin > f32::EPSILON {
+                let cot = to_i.dot(to_j) / sin;
+                laplacian[i] += (positions[j] - positions[i]) * cot;
+                laplacian[j] += (positions[i] - positions[j]) * cot;
+            }
+            areas[k] += normal.length() / 6.0;
+            normals[k] += normal;
+        }
+    }
+
+    (0..positions.len())
+        .map(|v| {
+            if half_edge_mesh.is_boundary_vertex(v) || areas[v] <= f32::EPSILON {
+                return 0.0;
+            }
+            // The Laplacian of the position points against the normal by twice the curvature
+            let laplacian = laplacian[v] / (2.0 * areas[v]);
+            -laplacian.dot(normals[v].normalize_or_zero()) * 0.5
+        })
+        .collect()
+}
+
+/// Edge collapse candidate for [`decimate`], ordered so the cheapest collapse is popped first
+/// from a max heap.
+struct EdgeCollapse {
+    cost: f64,
+    from: usize,
+    to: usize,
+    from_version: u32,
+    to_version: u32,
+}
+
+impl PartialEq for EdgeCollapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost.total_cmp(&other.cost).is_eq()
+    }
+}
+
+impl Eq for EdgeCollapse {}
+
+impl PartialOrd for EdgeCollapse {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EdgeCollapse {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Reduces a `TriangleList` mesh to about `ratio` of its triangles (at least one) by repeatedly
+/// collapsing the edge whose removal changes the surface least, measured with quadric error
+/// metrics. Each collapse merges one vertex into the other without moving it, so every attribute
+/// is kept as is, and collapses that would flip a triangle are skipped. Open edges are weighted so
+/// holes and borders keep their shape. Unused vertices are removed afterwards. Other topologies
+/// are left unchanged.
+///
+/// Collapses follow shared vertices, so use [`weld_vertices`] first on meshes with split
+/// vertices. Simplification stops early if no valid collapses remain.
+pub fn decimate(mesh: &mut Mesh, ratio: f32) {
+    const BOUNDARY_WEIGHT: f64 = 100.0;
+
+    fn face_normal(positions: &[Vec3], [a, b, c]: [usize; 3]) -> Vec3 {
+        (positions[b] - positions[a]).cross(positions[c] - positions[a])
+    }
+
+    fn plane_quadric(normal: Vec3, point: Vec3) -> DMat4 {
+        let normal = normal.normalize_or_zero().as_dvec3();
+        let plane = normal.extend(-normal.dot(point.as_dvec3()));
+        DMat4::from_cols(
+            plane * plane.x,
+            plane * plane.y,
+            plane * plane.z,
+            plane * plane.w,
+        )
+    }
+
+    fn push_collapse(
+        heap: &mut BinaryHeap<EdgeCollapse>,
+        quadrics: &[DMat4],
+        positions: &[Vec3],
+        versions: &[u32],
+        a: usize,
+        b: usize,
+    ) {
+        let quadric = quadrics[a] + quadrics[b];
+        let error = |p: Vec3| {
+            let p = p.as_dvec3().extend(1.0);
+            p.dot(quadric * p)
+        };
+        let (from, to, cost) = {
+            let (a_to_b, b_to_a) = (error(positions[b]), error(positions[a]));
+            if a_to_b <= b_to_a {
+                (a, b, a_to_b)
+            } else {
+                (b, a, b_to_a)
+            }
+        };
+        heap.push(EdgeCollapse {
+            cost,
+            from,
+            to,
+            from_version: versions[from],
+            to_version: versions[to],
+        });
+    }
+
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    let indices = triangle_list_indices(mesh);
+    let mut faces = indices
+        .chunks_exact(3)
+        .map(|tri| [tri[0], tri[1], tri[2]])
+        .collect::<Vec<_>>();
+    let target = ((faces.len() as f32 * ratio.clamp(0.0, 1.0)).round() as usize).max(1);
+
+    let mut quadrics = vec![DMat4::ZERO; positions.len()];
+    let mut vertex_faces = vec![Vec::new(); positions.len()];
+    let edge_counts = edge_use_counts(&indices);
+    for (f, tri) in faces.iter().enumerate() {
+        let normal = face_normal(&positions, *tri);
+        let quadric = plane_quadric(normal, positions[tri[0]]);
+        for (corner, v) in tri.iter().enumerate() {
+            quadrics[*v] += quadric;
+            vertex_faces[*v].push(f);
+
+            // Constrain open edges with a plane perpendicular to the face through the edge
+            let next = tri[(corner + 1) % 3];
+            if edge_counts[&[*v.min(&next), *v.max(&next)]] == 1 {
+                let edge = positions[next] - positions[*v];
+                let quadric = plane_quadric(edge.cross(normal), positions[*v]) * BOUNDARY_WEIGHT;
+                quadrics[*v] += quadric;
+                quadrics[next] += quadric;
+            }
+        }
+    }
+
+    let mut versions = vec![0; positions.len()];
+    let mut heap = BinaryHeap::new();
+    for [a, b] in edge_counts.keys() {
+        push_collapse(&mut heap, &quadrics, &positions, &versions, *a, *b);
+    }
+
+    let mut live = vec![true; faces.len()];
+    let mut live_count = faces.len();
+    while live_count > target {
+        let Some(collapse) = heap.pop() else {
+            break;
+        };
+        let (u, v) = (collapse.from, collapse.to);
+        if versions[u] != collapse.from_version || versions[v] != collapse.to_version {
+            continue;
+        }
+
+        let flips = vertex_faces[u]
+            .iter()
+            .filter(|f| live[**f] && !faces[**f].contains(&v))
+            .any(|f| {
+                let collapsed = faces[*f].map(|i| if i == u { v } else { i });
+                face_normal(&positions, faces[*f]).dot(face_normal(&positions, collapsed)) <= 0.0
+            });
+        let removed = vertex_faces[u]
+            .iter()
+            .filter(|f| live[**f] && faces[**f].contains(&v))
+            .count();
+        if flips || removed >= live_count {
+            continue;
+        }
+
+        for f in std::mem::take(&mut vertex_faces[u]) {
+            if !live[f] {
+                continue;
+            }
+            let tri = &mut faces[f];
+            for i in tri.iter_mut() {
+                if *i == u {
+                    *i = v;
+                }
+            }
+            if tri[0] == tri[1] || tri[1] == tri[2] || tri[2] == tri[0] {
+                live[f] = false;
+                live_count -= 1;
+            } else {
+                vertex_faces[v].push(f);
+            }
+        }
+        vertex_faces[v].retain(|f| live[*f]);
+        quadrics[v] = quadrics[v] + quadrics[u];
+        // Invalidates queued collapses of both vertices, `u` no longer exists
+        versions[u] += 1;
+        versions[v] += 1;
+
+        let neighbors = vertex_faces[v]
+            .iter()
+            .flat_map(|f| faces[*f])
+            .filter(|n| *n != v)
+            .collect::<HashSet<_>>();
+        for n in neighbors {
+            push_collapse(&mut heap, &quadrics, &positions, &versions, n, v);
+        }
+    }
+
+    let kept = faces
+        .iter()
+        .zip(&live)
+        .filter(|(_, live)| **live)
+        .flat_map(|(tri, _)| *tri);
+    match mesh.indices() {
+        Some(Indices::U16(_)) => {
+            mesh.insert_indices(Indices::U16(kept.map(|i| i as u16).collect()))
+        }
+        _ => mesh.insert_indices(Indices::U32(kept.map(|i| i as u32).collect())),
+    }
+    compact_vertices(mesh);
+}
+
+/// Simplifies a copy of `mesh` with [`decimate`] for each of `ratios`, returned in order of
+/// decreasing detail regardless of the order of `ratios`, ready for a distance based LOD
+/// system. Ratios are clamped to `0.0..=1.0` and every level keeps at least one triangle.
+pub fn generate_lods(mesh: &Mesh, ratios: &[f32]) -> Vec<Mesh> {
+    let mut ratios = ratios
+        .iter()
+        .map(|ratio| ratio.clamp(0.0, 1.0))
+        .collect::<Vec<_>>();
+    ratios.sort_by(|a, b| b.total_cmp(a));
+    ratios
+        .into_iter()
+        .map(|ratio| {
+            let mut lod = mesh.clone();
+            decimate(&mut lod, ratio);
+            lod
+        })
+        .collect()
+}
+
+/// Chains boundary half edges into closed loops of vertex indices, each following the direction
+/// of its edges. Chains that don't close are dropped.
+fn boundary_loops(edges: &[[usize; 2]]) -> Vec<Vec<usize>> {
+    let mut next: HashMap<usize, Vec<usize>> = HashMap::new();
+    for [a, b] in edges {
+        next.entry(*a).or_default().push(*b);
+    }
+
+    let mut loops = Vec::new();
+    for [start, _] in edges {
+        let mut boundary_loop = vec![*start];
+        let mut current = *start;
+        while let Some(b) = next.get_mut(&current).and_then(|b| b.pop()) {
+            if b == *start {
+                loops.push(std::mem::take(&mut boundary_loop));
+                break;
+            }
+            boundary_loop.push(b);
+            current = b;
+        }
+    }
+    loops.retain(|boundary_loop| boundary_loop.len() >= 3);
+    loops
+}
+
+/// Triangulates the polygon `vertices` (indices into `positions`) by ear clipping in its best fit
+/// plane, pushing triangles with the polygon's winding to `out`.
+fn ear_clip(positions: &[Vec3], vertices: &[usize], out: &mut Vec<usize>) {
+    // Newell's method, robust for non-planar and concave polygons
+    let mut normal = Vec3::ZERO;
+    for (i, a) in vertices.iter().enumerate() {
+        let (a, b) = (positions[*a], positions[vertices[(i + 1) % vertices.len()]]);
+        normal += (a - b).cross(a + b) * 0.5;
+    }
+    let normal = normal.normalize_or_zero();
+    let (u_axis, _) = planar_uv_axes(normal);
+    let w_axis = normal.cross(u_axis);
+    let project = |i: usize| Vec2::new(positions[i].dot(u_axis), positions[i].dot(w_axis));
+
+    let mut remaining = vertices.to_vec();
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let is_ear = |i: usize| {
+            let [a, b, c] = [(i + len - 1) % len, i, (i + 1) % len].map(|j| project(remaining[j]));
+            if (b - a).perp_dot(c - b) <= 0.0 {
+                return false;
+            }
+            remaining.iter().map(|j| project(*j)).all(|p| {
+                p == a
+                    || p == b
+                    || p == c
+                    || (b - a).perp_dot(p - a) < 0.0
+                    || (c - b).perp_dot(p - b) < 0.0
+                    || (a - c).perp_dot(p - c) < 0.0
+            })
+        };
+        // Degenerate polygons may have no ears, clip the first vertex anyway to make progress
+        let ear = (0..len).find(|i| is_ear(*i)).unwrap_or(0);
+        out.extend([
+            remaining[(ear + len - 1) % len],
+            remaining[ear],
+            remaining[(ear + 1) % len],
+        ]);
+        remaining.remove(ear);
+    }
+    out.extend(remaining);
+}
+
+/// Closes each hole in a `TriangleList` mesh by ear clipping the loop of boundary edges around
+/// it. No vertices are added, the new triangles use the existing boundary vertices and their
+/// attributes. Holes are found by vertex index, so use [`weld_vertices`] first on meshes with
+/// split vertices, otherwise every triangle is seen as its own hole. Non-indexed meshes and other
+/// topologies are left unchanged.
+///
+/// Loops that are highly non-planar or self-intersecting may fill imperfectly.
+pub fn fill_holes(mesh: &mut Mesh) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList || mesh.indices().is_none() {
+        return;
+    }
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    let mut new_indices = Vec::new();
+    for mut boundary_loop in boundary_loops(&boundary_half_edges(&triangle_list_indices(mesh))) {
+        // The fill walks each edge opposite to the triangle already using it
+        boundary_loop.reverse();
+        ear_clip(&positions, &boundary_loop, &mut new_indices);
+    }
+    match mesh.indices_mut() {
+        Some(Indices::U16(indices)) => indices.extend(new_indices.iter().map(|i| *i as u16)),
+        Some(Indices::U32(indices)) => indices.extend(new_indices.iter().map(|i| *i as u32)),
+        None => (),
+    }
+}
+
+/// Closes the open boundary left by a planar cut, such as from [`clip_by_plane`], with a flat cap.
+/// The boundary vertices are duplicated for the cap with normals along `plane_normal` (facing away
+/// from the mesh) and UV_0 projected onto the plane like [`project_uvs_planar`]. Other attributes
+/// are copied from the boundary, so regenerate tangents with [`compute_tangents`] if needed.
+///
+/// Assumes the boundary is a single loop lying in the plane, other holes get capped as if they
+/// were. Non-indexed meshes and other topologies are left unchanged.
+pub fn cap_boundary(mesh: &mut Mesh, plane_normal: Vec3) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList || mesh.indices().is_none() {
+        return;
+    }
+    let plane_normal = plane_normal.normalize_or_zero();
+    let (u_axis, v_axis) = planar_uv_axes(plane_normal);
+    let positions = mesh_positions(mesh).copied().collect::<Vec<_>>();
+    let vertex_count = positions.len();
+
+    let mut cap_vertices = Vec::new();
+    let mut cap_indices = Vec::new();
+    let mut cap_normals = Vec::new();
+    for mut boundary_loop in boundary_loops(&boundary_half_edges(&triangle_list_indices(mesh))) {
+        // The cap walks each edge opposite to the triangle already using it
+        boundary_loop.reverse();
+        let first = vertex_count + cap_vertices.len();
+        let local = (0..boundary_loop.len()).collect::<Vec<_>>();
+        let loop_positions = boundary_loop
+            .iter()
+            .map(|i| positions[*i])
+            .collect::<Vec<_>>();
+        let start = cap_indices.len();
+        ear_clip(&loop_positions, &local, &mut cap_indices);
+        for i in &mut cap_indices[start..] {
+            *i += first;
+        }
+
+        // Face the cap the same way as its winding, which points away from the mesh
+        let winding_normal = local
+            .iter()
+            .map(|i| {
+                let (a, b) = (loop_positions[*i], loop_positions[(i + 1) % local.len()]);
+                (a - b).cross(a + b)
+            })
+            .sum::<Vec3>();
+        let normal = if winding_normal.dot(plane_normal) < 0.0 {
+            -plane_normal
+        } else {
+            plane_normal
+        };
+        cap_normals.extend(std::iter::repeat(normal).take(boundary_loop.len()));
+        cap_vertices.extend(boundary_loop);
+    }
+    if cap_vertices.is_empty() {
+        return;
+    }
+
+    mesh_select_vertices(
+        mesh,
+        &(0..vertex_count).chain(cap_vertices).collect::<Vec<_>>(),
+    );
+    for (n, cap_normal) in mesh_normals_mut(mesh).skip(vertex_count).zip(cap_normals) {
+        *n = cap_normal;
+    }
+    let cap_positions = mesh_positions(mesh)
+        .skip(vertex_count)
+        .copied()
+        .collect::<Vec<_>>();
+    for (uv, p) in mesh_uvs_mut(mesh).skip(vertex_count).zip(cap_positions) {
+        *uv = Vec2::new(p.dot(u_axis), p.dot(v_axis));
+    }
+
+    let total = mesh.count_vertices();
+    if total > u16::MAX as usize {
+        convert_indices_u32(mesh);
+    }
+    match mesh.indices_mut() {
+        Some(Indices::U16(indices)) => indices.extend(cap_indices.iter().map(|i| *i as u16)),
+        Some(Indices::U32(indices)) => indices.extend(cap_indices.iter().map(|i| *i as u32)),
+        None => (),
+    }
+}
+
+/// Gives a surface thickness by adding a copy offset `thickness` against the vertex normals with
+/// flipped winding and normals, then bridging the boundary edges of the two layers. Smooth
+/// normals are computed first if the mesh has none. The bridging triangles share vertices with
+/// both layers. Only `TriangleList` meshes are supported, other topologies are returned
+/// unchanged.
+///
+/// Boundaries are found by vertex index, so use [`weld_vertices`] first on meshes with split
+/// vertices. Non-manifold input may produce artifacts.
+pub fn solidify(mesh: &Mesh, thickness: f32) -> Mesh {
+    let mut outer = mesh.clone();
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return outer;
+    }
+    if outer.attribute(Mesh::ATTRIBUTE_NORMAL).is_none() {
+        compute_smooth_normals(&mut outer);
+    }
+    ensure_indices(&mut outer);
+
+    let vertex_count = outer.count_vertices();
+    let boundary = boundary_half_edges(&triangle_list_indices(&outer));
+
+    let mut inner = outer.clone();
+    let normals = mesh_normals(&inner).copied().collect::<Vec<_>>();
+    for (p, n) in mesh_positions_mut(&mut inner).zip(&normals) {
+        *p -= *n * thickness;
+    }
+    flip_normals(&mut inner);
+    flip_winding(&mut inner);
+    // Both layers have the same attributes and topology
+    let _ = mesh_append(&mut outer, &inner);
+
+    // Walk each boundary edge backwards so the wall faces the same way as the outer layer
+    let wall = boundary.iter().flat_map(|[a, b]| {
+        let (inner_a, inner_b) = (a + vertex_count, b + vertex_count);
+        [*b, *a, inner_a, *b, inner_a, inner_b]
+    });
+    match outer.indices_mut() {
+        Some(Indices::U16(indices)) => indices.extend(wall.map(|i| i as u16)),
+        Some(Indices::U32(indices)) => indices.extend(wall.map(|i| i as u32)),
+        None => (),
+    }
+    outer
+}
+
+/// Reorders the triangles of an indexed `TriangleList` mesh to improve the GPU's post-transform
+/// vertex cache hit rate, using Tom Forsyth's linear-speed vertex cache optimization. Vertices
+/// are untouched, follow up with [`optimize_vertex_fetch`] to order them to match. Non-indexed
+/// meshes and other topologies are left unchanged.
+pub fn optimize_vertex_cache(mesh: &mut Mesh) {
+    const CACHE_SIZE: usize = 32;
+    const CACHE_DECAY_POWER: f32 = 1.5;
+    const LAST_TRIANGLE_SCORE: f32 = 0.75;
+    const VALENCE_BOOST_SCALE: f32 = 2.0;
+    const VALENCE_BOOST_POWER: f32 = 0.5;
+
+    fn vertex_score(cache_position: Option<usize>, remaining_triangles: usize) -> f32 {
+        if remaining_triangles == 0 {
+            return -1.0;
+        }
+        let cache_score = match cache_position {
+            None => 0.0,
+            // The most recent triangle's vertices get a fixed score so it isn't picked again
+            Some(position) if position < 3 => LAST_TRIANGLE_SCORE,
+            Some(position) => {
+                (1.0 - (position - 3) as f32 / (CACHE_SIZE - 3) as f32).powf(CACHE_DECAY_POWER)
+            }
+        };
+        // Favor vertices with few triangles left so they can leave the cache sooner
+        cache_score + VALENCE_BOOST_SCALE * (remaining_triangles as f32).powf(-VALENCE_BOOST_POWER)
+    }
+
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList || mesh.indices().is_none() {
+        return;
+    }
+    let indices = triangle_list_indices(mesh);
+    let triangle_count = indices.len() / 3;
+
+    let mut vertex_triangles = vec![Vec::new(); mesh.count_vertices()];
+    for (t, tri) in indices.chunks_exact(3).enumerate() {
+        for v in tri {
+            vertex_triangles[*v].push(t);
+        }
+    }
+    let mut cache_positions = vec![None; vertex_triangles.len()];
+    let mut scores = vertex_triangles
+        .iter()
+        .map(|triangles| vertex_score(None, triangles.len()))
+        .collect::<Vec<_>>();
+
+    let mut added = vec![false; triangle_count];
+    let mut order: Vec<usize> = Vec::with_capacity(indices.len());
+    let mut cache: Vec<usize> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut best = None;
+    let mut next_unadded = 0;
+    for _ in 0..triangle_count {
+        // Fall back to the next triangle in the original order when nothing in the cache helps
+        let t = best.take().unwrap_or_else(|| {
+            while added[next_unadded] {
+                next_unadded += 1;
+            }
+            next_unadded
+        });
+        added[t] = true;
+        let tri = &indices[t * 3..t * 3 + 3];
+        order.extend(tri);
+
+        for v in tri {
+            vertex_triangles[*v].retain(|other| *other != t);
+            cache.retain(|cached| cached != v);
+        }
+        let evicted = cache.len().saturating_sub(CACHE_SIZE - 3);
+        let evicted = cache.split_off(cache.len() - evicted);
+        cache.splice(0..0, tri.iter().copied());
+
+        for v in &evicted {
+            cache_positions[*v] = None;
+        }
+        for (position, v) in cache.iter().enumerate() {
+            cache_positions[*v] = Some(position);
+        }
+        let mut best_score = f32::NEG_INFINITY;
+        for v in cache.iter().chain(&evicted) {
+            scores[*v] = vertex_score(cache_positions[*v], vertex_triangles[*v].len());
+        }
+        for v in cache.iter().chain(&evicted) {
+            for other in &vertex_triangles[*v] {
+                let score = indices[other * 3..other * 3 + 3]
+                    .iter()
+                    .map(|v| scores[*v])
+                    .sum::<f32>();
+                if score > best_score {
+                    best_score = score;
+                    best = Some(*other);
+                }
+            }
+        }
+    }
+
+    match mesh.indices() {
+        Some(Indices::U16(_)) => {
+            mesh.insert_indices(Indices::U16(order.iter().map(|i| *i as u16).collect()))
+        }
+        _ => mesh.insert_indices(Indices::U32(order.iter().map(|i| *i as u32).collect())),
+    }
+}
+
+/// Reorders the vertices of an indexed mesh in the order the index buffer first uses them,
+/// improving memory locality when the GPU fetches them. Vertices the indices don't use are moved
+/// to the end. Does nothing for non-indexed meshes.
+pub fn optimize_vertex_fetch(mesh: &mut Mesh) {
+    let Some(indices) = mesh.indices() else {
+        return;
+    };
+    let vertex_count = mesh.count_vertices();
+    let mut remap = vec![usize::MAX; vertex_count];
+    let mut order = Vec::with_capacity(vertex_count);
+    for i in indices.iter().chain(0..vertex_count) {
+        if remap[i] == usize::MAX {
+            remap[i] = order.len();
+            order.push(i);
+        }
+    }
+    mesh_select_vertices(mesh, &order);
+    remap_indices(mesh, &remap);
+}
+
+/// Applies `uv * scale + offset` to every UV_0 coordinate.
+pub fn transform_uvs(mesh: &mut Mesh, scale: Vec2, offset: Vec2) {
+    for uv in mesh_uvs_mut(mesh) {
+        *uv = *uv * scale + offset;
+    }
+}
+
+/// Rotates every UV_0 coordinate by `radians` around (0.5, 0.5).
+pub fn rotate_uvs(mesh: &mut Mesh, radians: f32) {
+    let rotation = Vec2::from_angle(radians);
+    let center = Vec2::splat(0.5);
+    for uv in mesh_uvs_mut(mesh) {
+        *uv = rotation.rotate(*uv - center) + center;
+    }
+}
+
+/// U and V directions used by [`project_uvs_planar`] for a projection along `axis`.
+fn planar_uv_axes(axis: Vec3) -> (Vec3, Vec3) {
+    let axis = axis.normalize_or_zero();
+    let reference = if axis.x.abs() > 0.99 {
+        Vec3::Z
+    } else {
+        Vec3::X
+    };
+    let u_axis = (reference - axis * reference.dot(axis)).normalize_or_zero();
+    (u_axis, u_axis.cross(axis))
+}
+
+/// Generates UV_0 by projecting each position onto the plane orthogonal to `axis` and multiplying
+/// by `scale`. The U direction follows X (or Z when `axis` is close to X) and V is
+/// `u.cross(axis)`, so projecting along Y gives `uv = (x, z) * scale`.
+pub fn project_uvs_planar(mesh: &mut Mesh, axis: Vec3, scale: f32) {
+    let (u_axis, v_axis) = planar_uv_axes(axis);
+    let uvs = mesh_positions(mesh)
+        .map(|p| [p.dot(u_axis) * scale, p.dot(v_axis) * scale])
+        .collect::<Vec<_>>();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+}
+
+/// Generates UV_0 from the longitude (U) and latitude (V) of the direction from `center` to each
+/// position, with V = 0 at +Y. Triangles crossing the seam behind the center (-Z) interpolate
+/// across the whole texture, and UVs pinch together at the poles, so expect visible artifacts
+/// there unless the mesh has split vertices along the seam.
+pub fn project_uvs_spherical(mesh: &mut Mesh, center: Vec3) {
+    let uvs = mesh_positions(mesh)
+        .map(|p| {
+            let dir = (*p - center).normalize_or_zero();
+            [
+                0.5 + dir.x.atan2(dir.z) / std::f32::consts::TAU,
+                dir.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI,
+            ]
+        })
+        .collect::<Vec<_>>();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+}
+
+/// Generates UV_0 with a planar projection per vertex (see [`project_uvs_planar`]) along
+/// whichever of X, Y or Z its normal is closest to. Meshes without normals are projected along
+/// Y.
+pub fn project_uvs_triplanar(mesh: &mut Mesh, scale: f32) {
+    let normals = mesh_normals_slice(mesh);
+    let uvs = mesh_positions(mesh)
+        .enumerate()
+        .map(|(i, p)| {
+            let n = normals.get(i).map_or(Vec3::ZERO, |n| n.abs());
+            let axis = if n.x > n.y && n.x > n.z {
+                Vec3::X
+            } else if n.z > n.y {
+                Vec3::Z
+            } else {
+                Vec3::Y
+            };
+            let (u_axis, v_axis) = planar_uv_axes(axis);
+            [p.dot(u_axis) * scale, p.dot(v_axis) * scale]
+        })
+        .collect::<Vec<_>>();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+}
+
+/// Inserts or overwrites `Mesh::ATTRIBUTE_COLOR` as `Float32x4` with `color` for every vertex.
+pub fn set_vertex_color(mesh: &mut Mesh, color: LinearRgba) {
+    let colors = vec![color.to_f32_array(); mesh_len(mesh)];
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+}
+
+/// Inserts or overwrites `Mesh::ATTRIBUTE_COLOR` as `Float32x4` with the color `f` returns for
+/// each vertex position.
+pub fn fill_vertex_colors_from<F: Fn(Vec3) -> LinearRgba>(mesh: &mut Mesh, f: F) {
+    let colors = mesh_positions(mesh)
+        .map(|p| f(*p).to_f32_array())
+        .collect::<Vec<_>>();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+}
+
+/// Converts the RGB of `Mesh::ATTRIBUTE_COLOR` from sRGB to linear, for colors authored in sRGB
+/// on meshes that should match glTF's linear vertex colors. Alpha is left unchanged, and 8-bit
+/// colors keep their format. Does nothing if the mesh has no colors.
+pub fn vertex_colors_srgb_to_linear(mesh: &mut Mesh) {
+    map_vertex_color_channels(mesh, Srgba::gamma_function);
+}
+
+/// Inverse of [`vertex_colors_srgb_to_linear`].
+pub fn vertex_colors_linear_to_srgb(mesh: &mut Mesh) {
+    map_vertex_color_channels(mesh, Srgba::gamma_function_inverse);
+}
+
+fn map_vertex_color_channels(mesh: &mut Mesh, f: fn(f32) -> f32) {
+    let mut colors = mesh_colors(mesh);
+    if colors.is_empty() {
+        return;
+    }
+    for c in &mut colors {
+        *c = Vec3::from_array(c.xyz().to_array().map(f)).extend(c.w);
+    }
+    mesh_set_colors(mesh, &colors);
+}
+
+/// Replaces every position `p` with `f(p)`.
+pub fn map_positions<F: FnMut(Vec3) -> Vec3>(mesh: &mut Mesh, mut f: F) {
+    for p in mesh_positions_mut(mesh) {
+        *p = f(*p);
+    }
+}
+
+/// Replaces every normal `n` with `f(n)`. The result is not re-normalized.
+pub fn map_normals<F: FnMut(Vec3) -> Vec3>(mesh: &mut Mesh, mut f: F) {
+    for n in mesh_normals_mut(mesh) {
+        *n = f(*n);
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MeshBuildError {
+    #[error("Attribute {attribute:?} has {found} values but there are {expected} positions.")]
+    AttributeLengthMismatch {
+        attribute: MeshVertexAttributeId,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Index {index} is out of range for {vertex_count} vertices.")]
+    IndexOutOfRange { index: u32, vertex_count: usize },
+}
+
+/// Builds a mesh from its parts, checking that the attribute lengths match and the indices are in
+/// range before creating it.
+#[derive(Clone, Debug)]
+pub struct MeshBuilder {
+    topology: PrimitiveTopology,
+    positions: Vec<Vec3>,
+    normals: Option<Vec<Vec3>>,
+    uvs: Option<Vec<Vec2>>,
+    indices: Option<Vec<u32>>,
+}
+
+impl Default for MeshBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MeshBuilder {
+    /// Starts an empty `TriangleList` mesh.
+    pub fn new() -> Self {
+        Self {
+            topology: PrimitiveTopology::TriangleList,
+            positions: Vec::new(),
+            normals: None,
+            uvs: None,
+            indices: None,
+        }
+    }
+
+    pub fn topology(mut self, topology: PrimitiveTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn positions(mut self, positions: Vec<Vec3>) -> Self {
+        self.positions = positions;
+        self
+    }
+
+    pub fn normals(mut self, normals: Vec<Vec3>) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    pub fn uvs(mut self, uvs: Vec<Vec2>) -> Self {
+        self.uvs = Some(uvs);
+        self
+    }
+
+    pub fn indices(mut self, indices: Vec<u32>) -> Self {
+        self.indices = Some(indices);
+        self
+    }
+
+    pub fn build(self) -> Result<Mesh, MeshBuildError> {
+        let vertex_count = self.positions.len();
+        let lengths = [
+            (
+                Mesh::ATTRIBUTE_NORMAL.id,
+                self.normals.as_ref().map(Vec::len),
+            ),
+            (Mesh::ATTRIBUTE_UV_0.id, self.uvs.as_ref().map(Vec::len)),
+        ];
+        for (attribute, len) in lengths {
+            if let Some(found) = len.filter(|len| *len != vertex_count) {
+                return Err(MeshBuildError::AttributeLengthMismatch {
+                    attribute,
+                    expected: vertex_count,
+                    found,
+                });
+            }
+        }
+        if let Some(index) = self
+            .indices
+            .iter()
+            .flatten()
+            .find(|i| **i as usize >= vertex_count)
+        {
+            return Err(MeshBuildError::IndexOutOfRange {
+                index: *index,
+                vertex_count,
+            });
+        }
+
+        let mut mesh = Mesh::new(self.topology, RenderAssetUsages::default());
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            self.positions
+                .iter()
+                .map(|p| p.to_array())
+                .collect::<Vec<_>>(),
+        );
+        if let Some(normals) = self.normals {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_NORMAL,
+                normals.iter().map(|n| n.to_array()).collect::<Vec<_>>(),
+            );
+        }
+        if let Some(uvs) = self.uvs {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_UV_0,
+                uvs.iter().map(|uv| uv.to_array()).collect::<Vec<_>>(),
+            );
+        }
+        if let Some(indices) = self.indices {
+            mesh.insert_indices(Indices::U32(indices));
+        }
+        Ok(mesh)
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MeshValidationError {
+    #[error("Attribute {attribute:?} has {found} values but there are {expected} positions.")]
+    AttributeLengthMismatch {
+        attribute: MeshVertexAttributeId,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Index {index} is out of range for {vertex_count} vertices.")]
+    IndexOutOfRange { index: usize, vertex_count: usize },
+    #[error(
+        "TriangleList has {0} indices (or vertices if non-indexed), which is not a multiple of 3."
+    )]
+    IncompleteTriangle(usize),
+    #[error("Joint index {joint_index} is out of range for {joint_count} joints.")]
+    JointIndexOutOfRange {
+        joint_index: u16,
+        joint_count: usize,
+    },
+}
+
+/// Checks that every attribute has one value per position, that the indices are in range and
+/// that a `TriangleList` has whole triangles. All problems found are returned, though only the
+/// first out of range index is reported.
+pub fn validate_mesh(mesh: &Mesh) -> Result<(), Vec<MeshValidationError>> {
+    let mut errors = Vec::new();
+    let vertex_count = mesh_len(mesh);
+
+    for (attribute, values) in mesh.attributes() {
+        if values.len() != vertex_count {
+            errors.push(MeshValidationError::AttributeLengthMismatch {
+                attribute,
+                expected: vertex_count,
+                found: values.len(),
+            });
+        }
+    }
+
+    if let Some(indices) = mesh.indices() {
+        if let Some(index) = indices.iter().find(|i| *i >= vertex_count) {
+            errors.push(MeshValidationError::IndexOutOfRange {
+                index,
+                vertex_count,
+            });
+        }
+    }
+
+    if mesh.primitive_topology() == PrimitiveTopology::TriangleList {
+        let count = mesh.indices().map_or(vertex_count, Indices::len);
+        if count % 3 != 0 {
+            errors.push(MeshValidationError::IncompleteTriangle(count));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Same as [`validate_mesh`], but also checks that the joint indices are in range for
+/// `inverse_bindposes`.
+pub fn validate_skinned_mesh(
+    mesh: &Mesh,
+    inverse_bindposes: &SkinnedMeshInverseBindposes,
+) -> Result<(), Vec<MeshValidationError>> {
+    let mut errors = validate_mesh(mesh).err().unwrap_or_default();
+    let joint_count = inverse_bindposes.len();
+    if let Some(joint_index) = mesh_joint_indices(mesh)
+        .flatten()
+        .find(|i| **i as usize >= joint_count)
+    {
+        errors.push(MeshValidationError::JointIndexOutOfRange {
+            joint_index: *joint_index,
+            joint_count,
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum IndexConversionError {
+    #[error("Index {0} does not fit in a u16.")]
+    IndexOverflow(u32),
+}
+
+/// Converts `Indices::U16` to `Indices::U32`. Does nothing if the mesh has no indices or already
+/// uses U32.
+pub fn convert_indices_u32(mesh: &mut Mesh) {
+    if let Some(Indices::U16(indices)) = mesh.indices() {
+        let indices = indices.iter().map(|i| *i as u32).collect();
+        mesh.insert_indices(Indices::U32(indices));
+    }
+}
+
+/// Converts `Indices::U32` to `Indices::U16`, leaving the mesh unchanged and returning an error
+/// if any index is too large. Does nothing if the mesh has no indices or already uses U16.
+pub fn convert_indices_u16(mesh: &mut Mesh) -> Result<(), IndexConversionError> {
+    if let Some(Indices::U32(indices)) = mesh.indices() {
+        let indices = indices
+            .iter()
+            .map(|i| u16::try_from(*i).map_err(|_| IndexConversionError::IndexOverflow(*i)))
+            .collect::<Result<_, _>>()?;
+        mesh.insert_indices(Indices::U16(indices));
+    }
+    Ok(())
+}
+
+/// Converts `Float32x3` normals to `Snorm16x4`, halving their size at a precision of about
+/// 1/32767. The attribute keeps its id but is re-inserted with the new format, so the accessors
+/// such as [`mesh_normals`] won't see it until [`dequantize_normals`] is called. Does nothing if
+/// the normals aren't `Float32x3`.
+pub fn quantize_normals_snorm16(mesh: &mut Mesh) {
+    let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    else {
+        return;
+    };
+    let quantized = normals
+        .iter()
+        .map(|n| {
+            let [x, y, z] = n.map(|x| (x.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16);
+            [x, y, z, 0]
+        })
+        .collect();
+    mesh.insert_attribute(
+        MeshVertexAttribute {
+            format: VertexFormat::Snorm16x4,
+            ..Mesh::ATTRIBUTE_NORMAL
+        },
+        VertexAttributeValues::Snorm16x4(quantized),
+    );
+}
+
+/// Converts `Snorm16x4` normals from [`quantize_normals_snorm16`] back to normalized
+/// `Float32x3`. Does nothing if the normals aren't `Snorm16x4`.
+pub fn dequantize_normals(mesh: &mut Mesh) {
+    let Some(VertexAttributeValues::Snorm16x4(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    else {
+        return;
+    };
+    let normals = normals
+        .iter()
+        .map(|n| {
+            Vec3::new(n[0] as f32, n[1] as f32, n[2] as f32)
+                .normalize_or_zero()
+                .to_array()
+        })
+        .collect::<Vec<_>>();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+}
+
+/// Converts `Float32x2` UV_0 to `Unorm16x2`, halving their size at a precision of about 1/65535.
+/// Coordinates are clamped to `0.0..=1.0`, so don't use this on meshes with tiling UVs. The
+/// attribute keeps its id but is re-inserted with the new format, so the accessors such as
+/// [`mesh_uvs`] won't see it until [`dequantize_uvs`] is called. Does nothing if UV_0 isn't
+/// `Float32x2`.
+pub fn quantize_uvs_unorm16(mesh: &mut Mesh) {
+    let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else {
+        return;
+    };
+    let quantized = uvs
+        .iter()
+        .map(|uv| uv.map(|x| (x.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16))
+        .collect();
+    mesh.insert_attribute(
+        MeshVertexAttribute {
+            format: VertexFormat::Unorm16x2,
+            ..Mesh::ATTRIBUTE_UV_0
+        },
+        VertexAttributeValues::Unorm16x2(quantized),
+    );
+}
+
+/// Converts `Unorm16x2` UV_0 from [`quantize_uvs_unorm16`] back to `Float32x2`. Does nothing if
+/// UV_0 isn't `Unorm16x2`.
+pub fn dequantize_uvs(mesh: &mut Mesh) {
+    let Some(VertexAttributeValues::Unorm16x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else {
+        return;
+    };
+    let uvs = uvs
+        .iter()
+        .map(|uv| uv.map(|x| x as f32 / u16::MAX as f32))
+        .collect::<Vec<_>>();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+}
+
+/// Gives a non-indexed mesh sequential indices, using `Indices::U16` when the vertex count
+/// allows it and `Indices::U32` otherwise. Does nothing if the mesh already has indices.
+pub fn ensure_indices(mesh: &mut Mesh) {
+    if mesh.indices().is_some() {
+        return;
+    }
+    let vertex_count = mesh.count_vertices();
+    if vertex_count <= u16::MAX as usize {
+        mesh.insert_indices(Indices::U16((0..vertex_count as u16).collect()));
+    } else {
+        mesh.insert_indices(Indices::U32((0..vertex_count as u32).collect()));
+    }
+}
+
+/// Per-vertex data yielded by [`mesh_vertices_indexed`]. Attributes the mesh doesn't have are
+/// `None`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vertex {
+    pub position: Vec3,
+    pub normal: Option<Vec3>,
+    pub uv: Option<Vec2>,
+    /// Normalized color, see [`mesh_colors`].
+    pub color: Option<Vec4>,
+}
+
+/// Iterates the corners of each triangle with their position, normal, UV_0 and color bundled
+/// together. Yields nothing for topologies other than `TriangleList`.
+pub fn mesh_vertices_indexed(mesh: &Mesh) -> impl Iterator<Item = [Vertex; 3]> + '_ {
+    let positions = mesh_positions_slice(mesh);
+    let normals = mesh_normals_slice(mesh);
+    let uvs = mesh_uvs_slice(mesh);
+    let colors = mesh_colors(mesh);
+    let indices = if mesh.primitive_topology() == PrimitiveTopology::TriangleList {
+        triangle_list_indices(mesh)
+    } else {
+        Vec::new()
+    };
+    (0..indices.len() / 3).map(move |t| {
+        [0, 1, 2].map(|corner| {
+            let i = indices[t * 3 + corner];
+            Vertex {
+                position: positions[i],
+                normal: normals.get(i).copied(),
+                uv: uvs.get(i).copied(),
+                color: colors.get(i).copied(),
+            }
+        })
+    })
+}
+
+/// Rounds every position component to the nearest multiple of `grid`.
+pub fn snap_positions(mesh: &mut Mesh, grid: f32) {
+    for p in mesh_positions_mut(mesh) {
+        *p = (*p / grid).round() * grid;
+    }
+}
+
+/// Moves each vertex along its normal by `amount(position)`. Does nothing if the mesh has no
+/// normals. The normals are not updated, follow up with [`compute_smooth_normals`] or
+/// [`compute_flat_normals`].
+pub fn displace_along_normals<F: Fn(Vec3) -> f32>(mesh: &mut Mesh, amount: F) {
+    let normals = mesh_normals(mesh).copied().collect::<Vec<_>>();
+    for (p, n) in mesh_positions_mut(mesh).zip(normals) {
+        *p += n * amount(*p);
+    }
+}
+
+/// Moves each vertex toward its projection onto the sphere of `radius` around `center`, by `t`
+/// (0.0 leaves it unchanged, 1.0 puts it on the sphere). Vertices at `center` are left in place.
+/// The normals are not updated, follow up with [`compute_smooth_normals`].
+pub fn spherify(mesh: &mut Mesh, center: Vec3, radius: f32, t: f32) {
+    for p in mesh_positions_mut(mesh) {
+        let dir = (*p - center).normalize_or_zero();
+        if dir != Vec3::ZERO {
+            *p = p.lerp(center + dir * radius, t);
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Expected {expected} vertices but found {found}.")]
+pub struct VertexCountMismatchError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+/// Adds `weight * (target - base)` for each target to the base positions, and to the normals
+/// (which are then re-normalized) when both meshes have them. Targets must share the base mesh's
+/// vertex ordering. Returns an error without changing `base` if any target's vertex count
+/// differs.
+pub fn apply_morph_targets(
+    base: &mut Mesh,
+    targets: &[(&Mesh, f32)],
+) -> Result<(), VertexCountMismatchError> {
+    let expected = mesh_len(base);
+    if let Some((target, _)) = targets.iter().find(|(t, _)| mesh_len(t) != expected) {
+        return Err(VertexCountMismatchError {
+            expected,
+            found: mesh_len(target),
+        });
+    }
+
+    let base_positions = mesh_positions_slice(base).to_vec();
+    let base_normals = mesh_normals_slice(base).to_vec();
+    let mut positions = base_positions.clone();
+    let mut normals = base_normals.clone();
+    for (target, weight) in targets {
+        for ((p, base), target) in positions
+            .iter_mut()
+            .zip(&base_positions)
+            .zip(mesh_positions(target))
+        {
+            *p += (*target - *base) * *weight;
+        }
+        for ((n, base), target) in normals
+            .iter_mut()
+            .zip(&base_normals)
+            .zip(mesh_normals(target))
+        {
+            *n += (*target - *base) * *weight;
+        }
+    }
+
+    for (p, morphed) in mesh_positions_mut(base).zip(positions) {
+        *p = morphed;
+    }
+    for (n, morphed) in mesh_normals_mut(base).zip(normals) {
+        *n = morphed.normalize_or_zero();
+    }
+    Ok(())
+}
+
+/// Per-vertex `curr - prev` position deltas. Both meshes must share vertex ordering, for example
+/// both coming from [`mesh_with_skinned_transform`] on consecutive frames.
+pub fn motion_vectors(prev: &Mesh, curr: &Mesh) -> Result<Vec<Vec3>, VertexCountMismatchError> {
+    if mesh_len(prev) != mesh_len(curr) {
+        return Err(VertexCountMismatchError {
+            expected: mesh_len(prev),
+            found: mesh_len(curr),
+        });
+    }
+    Ok(mesh_positions(prev)
+        .zip(mesh_positions(curr))
+        .map(|(prev, curr)| *curr - *prev)
+        .collect())
+}
+
+pub fn f32x3_vec3_iter_mut(v: IterMut<[f32; 3]>) -> IterMut<Vec3> {
+    bytemuck::cast_slice_mut::<[f32; 3], Vec3>(v.into_slice()).iter_mut()
+}
+
+pub fn f32x3_vec3_iter(v: Iter<[f32; 3]>) -> Iter<Vec3> {
+    bytemuck::cast_slice::<[f32; 3], Vec3>(v.as_slice()).iter()
+}
+
+/// Copies each value out, as `Vec4` can be more strictly aligned than `[f32; 4]`.
+pub fn f32x4_vec4_iter(v: Iter<[f32; 4]>) -> impl ExactSizeIterator<Item = Vec4> + '_ {
+    v.map(|v| Vec4::from(*v))
+}
+
+pub fn f32x3_vec3_iter_mut2(v: IterMut<[f32; 3]>) -> IterMut<Vec3> {
+    bytemuck::cast_slice_mut::<[f32; 3], Vec3>(v.into_slice()).iter_mut()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns true if every triangle's winding agrees with the stored normals of its vertices.
+    fn winding_matches_normals(mesh: &Mesh) -> bool {
+        let positions = mesh_positions_slice(mesh);
+        let normals = mesh_normals_slice(mesh);
+        triangle_list_indices(mesh).chunks_exact(3).all(|tri| {
+            let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| positions[i]);
+            let face_normal = (b - a).cross(c - a);
+            tri.iter().all(|i| face_normal.dot(normals[*i]) > 0.0)
+        })
+    }
+
+    fn triangle_mesh(positions: Vec<[f32; 3]>, indices: Vec<u32>) -> Mesh {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_indices(Indices::U32(indices));
+        mesh
+    }
+
+    fn triangle_count(mesh: &Mesh) -> usize {
+        triangle_list_indices(mesh).len() / 3
+    }
+
+    fn cube() -> Mesh {
+        Mesh::from(Cuboid::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn split_vertices_at_uv_seams_cube_cross() {
+        // Unit cube with one vertex per corner, vertex x + 2y + 4z at (x, y, z), unwrapped into a
+        // cross with front (+Z) in the middle, top, bottom and back above and below it and the
+        // sides either side. Only the four front corners have the same UV on all their faces.
+        let positions = (0..8)
+            .map(|i| [(i & 1) as f32, ((i >> 1) & 1) as f32, (i >> 2) as f32])
+            .collect::<Vec<_>>();
+        // Front, top, bottom, back, left and right
+        let faces = [
+            [4, 5, 7, 6],
+            [6, 7, 3, 2],
+            [0, 1, 5, 4],
+            [1, 0, 2, 3],
+            [0, 4, 6, 2],
+            [5, 1, 3, 7],
+        ];
+        let unwrap = |face: usize, c: u32| {
+            let [x, y, z] = [c & 1, (c >> 1) & 1, c >> 2].map(|v| v as f32);
+            match face {
+                0 => Vec2::new(1.0 + x, 2.0 - y),
+                1 => Vec2::new(1.0 + x, z),
+                2 => Vec2::new(1.0 + x, 3.0 - z),
+                3 => Vec2::new(1.0 + x, 3.0 + y),
+                4 => Vec2::new(z, 2.0 - y),
+                _ => Vec2::new(3.0 - z, 2.0 - y),
+            }
+        };
+        let mut indices = Vec::new();
+        let mut corner_uvs = Vec::new();
+        for (face, quad) in faces.iter().enumerate() {
+            for i in [0, 1, 2, 0, 2, 3] {
+                indices.push(quad[i]);
+                corner_uvs.push(unwrap(face, quad[i]));
+            }
+        }
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions.clone());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, positions.clone());
+        mesh.insert_indices(Indices::U32(indices));
+        let mut unchanged = mesh.clone();
+        let error = split_vertices_at_uv_seams(&mut unchanged, &corner_uvs[1..]).unwrap_err();
+        assert_eq!((error.expected, error.found), (36, 35));
+        assert_eq!(mesh_len(&unchanged), 8);
+        assert!(mesh_uvs_slice(&unchanged).is_empty());
+
+        split_vertices_at_uv_seams(&mut mesh, &corner_uvs).unwrap();
+
+        // The classic cross unwrap of a cube has 14 distinct UVs
+        assert_eq!(mesh_len(&mesh), 14);
+        let copies_of = |p: [f32; 3]| mesh_positions(&mesh).filter(|q| q.to_array() == p).count();
+        for (i, p) in positions.iter().enumerate() {
+            let expected = match i {
+                4..=7 => 1,
+                0 | 1 => 2,
+                _ => 3,
+            };
+            assert_eq!(copies_of(*p), expected, "corner {i}");
+        }
+        // Copies keep the other attributes, and every corner gets the UV it asked for
+        let normals = mesh_normals_slice(&mesh);
+        for (p, n) in mesh_positions(&mesh).zip(normals) {
+            assert_eq!(p, n);
+        }
+        let uvs = mesh_uvs_slice(&mesh);
+        for (i, uv) in mesh.indices().unwrap().iter().zip(&corner_uvs) {
+            assert_eq!(uvs[i], *uv);
+        }
+        for (p, original) in mesh_positions(&mesh).zip(&positions) {
+            assert_eq!(p.to_array(), *original);
+        }
+    }
+
+    #[test]
+    fn mesh_with_transform_rotates_tangents() {
+        let mut mesh = cube();
+        compute_tangents(&mut mesh).unwrap();
+        let rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let rotated = mesh_with_transform(&mesh, &Transform::from_rotation(rotation));
+
+        let tangents = mesh_tangents(&mesh).collect::<Vec<_>>();
+        let rotated_tangents = mesh_tangents(&rotated).collect::<Vec<_>>();
+        assert_eq!(tangents.len(), mesh_len(&mesh));
+        for (before, after) in tangents.iter().zip(&rotated_tangents) {
+            assert!((rotation * before.xyz()).abs_diff_eq(after.xyz(), 1e-5));
+            assert_eq!(before.w, after.w);
+        }
+    }
+
+    #[test]
+    fn compute_flat_normals_quad_faces_z() {
+        let mut mesh = Mesh::from(Rectangle::new(1.0, 1.0));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 0.0]; 4]);
+        compute_flat_normals(&mut mesh);
+        assert_eq!(mesh_normals(&mesh).count(), mesh_len(&mesh));
+        for n in mesh_normals(&mesh) {
+            assert!(n.abs_diff_eq(Vec3::Z, 1e-6));
+        }
+    }
+
+    #[test]
+    fn compute_smooth_normals_hemisphere_follows_surface() {
+        let mut mesh = Sphere::new(1.0).mesh().ico(3).unwrap();
+        mesh.remove_attribute(Mesh::ATTRIBUTE_NORMAL);
+        compute_smooth_normals(&mut mesh);
+        // Faceted normals would be off the radial direction by up to half a face's angle
+        for (p, n) in mesh_positions(&mesh).zip(mesh_normals(&mesh)) {
+            if p.y >= 0.0 {
+                assert!(n.dot(p.normalize()) > 0.999);
+            }
+        }
+    }
+
+    #[test]
+    fn deindex_mesh_gives_one_vertex_per_index() {
+        let mut mesh = cube();
+        let index_count = mesh.indices().unwrap().len();
+        deindex_mesh(&mut mesh);
+        assert!(mesh.indices().is_none());
+        assert_eq!(mesh_len(&mesh), index_count);
+        assert_eq!(mesh_uvs(&mesh).count(), index_count);
+    }
+
+    #[test]
+    fn mesh_surface_area_unit_quad() {
+        assert_eq!(
+            mesh_surface_area(&Mesh::from(Rectangle::new(1.0, 1.0))),
+            1.0
+        );
+    }
+
+    #[test]
+    fn mesh_volume_unit_cube() {
+        assert!((mesh_volume(&cube()) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn subdivide_splits_each_triangle_into_four() {
+        let mut mesh = triangle_mesh(
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            vec![0, 1, 2],
+        );
+        subdivide(&mut mesh, 1);
+        assert_eq!(triangle_count(&mesh), 4);
+        // Shared midpoints are only added once
+        assert_eq!(mesh_len(&mesh), 6);
+        subdivide(&mut mesh, 1);
+        assert_eq!(triangle_count(&mesh), 16);
+        assert_eq!(mesh_len(&mesh), 15);
+    }
+
+    #[test]
+    fn mesh_append_rejects_mismatched_topology() {
+        let mut lines = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default());
+        lines.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+        );
+        let result = mesh_append(&mut lines, &cube());
+        assert!(matches!(
+            result,
+            Err(MeshAppendError::TopologyMismatch {
+                dest: PrimitiveTopology::LineList,
+                src: PrimitiveTopology::TriangleList,
+            })
+        ));
+        assert_eq!(mesh_len(&lines), 2);
+    }
+
+    #[test]
+    fn mesh_append_upgrades_u16_indices_on_overflow() {
+        let points = |count: u16| {
+            let mut mesh = Mesh::new(PrimitiveTopology::PointList, RenderAssetUsages::default());
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                vec![[0.0, 0.0, 0.0]; count as usize],
+            );
+            mesh.insert_indices(Indices::U16((0..count).collect()));
+            mesh
+        };
+        let mut dest = points(40_000);
+        mesh_append(&mut dest, &points(30_000)).unwrap();
+
+        let Some(Indices::U32(indices)) = dest.indices() else {
+            panic!("indices should have been upgraded to U32");
+        };
+        assert_eq!(indices.len(), 70_000);
+        assert!(indices
+            .iter()
+            .enumerate()
+            .all(|(i, index)| *index == i as u32));
+    }
+
+    #[test]
+    fn skin_mesh_in_place_rotates_tangents() {
+        let mut mesh = triangle_mesh(vec![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], Vec::new());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, vec![[1.0, 0.0, 0.0, -1.0]; 2]);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            VertexAttributeValues::Uint16x4(vec![[0, 0, 0, 0], [1, 0, 0, 0]]),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, vec![[1.0, 0.0, 0.0, 0.0]; 2]);
+
+        let rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        skin_mesh_in_place(&mut mesh, &[Mat4::IDENTITY, Mat4::from_quat(rotation)]);
+
+        let tangents = mesh_tangents(&mesh).collect::<Vec<_>>();
+        assert!(tangents[0].abs_diff_eq(Vec4::new(1.0, 0.0, 0.0, -1.0), 1e-6));
+        assert!(tangents[1].abs_diff_eq(Vec4::new(0.0, 1.0, 0.0, -1.0), 1e-6));
+    }
+
+    #[test]
+    fn project_uvs_planar_grid_on_xz() {
+        let mut mesh = Mesh::from(Plane3d::default().mesh().size(4.0, 4.0).subdivisions(3));
+        project_uvs_planar(&mut mesh, Vec3::Y, 0.5);
+        for (p, uv) in mesh_positions(&mesh).zip(mesh_uvs(&mesh)) {
+            assert_eq!(*uv, Vec2::new(p.x, p.z) * 0.5);
+        }
+    }
+
+    #[test]
+    fn convert_indices_between_u16_and_u32() {
+        let mut mesh = cube();
+        let indices = mesh.indices().unwrap().iter().collect::<Vec<_>>();
+        convert_indices_u16(&mut mesh).unwrap();
+        assert!(matches!(mesh.indices(), Some(Indices::U16(_))));
+        convert_indices_u32(&mut mesh);
+        assert!(matches!(mesh.indices(), Some(Indices::U32(_))));
+        assert_eq!(mesh.indices().unwrap().iter().collect::<Vec<_>>(), indices);
+    }
+
+    #[test]
+    fn convert_indices_u16_overflow() {
+        let mut mesh = cube();
+        mesh.insert_indices(Indices::U32(vec![0, 1, 70_000]));
+        assert!(matches!(
+            convert_indices_u16(&mut mesh),
+            Err(IndexConversionError::IndexOverflow(70_000))
+        ));
+        assert!(matches!(mesh.indices(), Some(Indices::U32(_))));
+    }
+
+    #[test]
+    fn snap_positions_lands_on_grid() {
+        let mut mesh = triangle_mesh(
+            vec![[0.13, -0.9, 2.51], [-1.37, 0.2, 0.0], [0.76, 3.3, -0.01]],
+            vec![0, 1, 2],
+        );
+        let original = mesh_positions(&mesh).copied().collect::<Vec<_>>();
+        snap_positions(&mut mesh, 0.25);
+        for (p, original) in mesh_positions(&mesh).zip(original) {
+            let steps = *p / 0.25;
+            assert_eq!(steps, steps.round());
+            assert!((*p - original).abs().max_element() <= 0.125);
+        }
+    }
+
+    #[test]
+    fn apply_morph_targets_half_weight() {
+        let grid = |bump: f32| {
+            let positions = (0..9)
+                .map(|i| {
+                    let y = if i == 4 { bump } else { 0.0 };
+                    [(i % 3) as f32, y, (i / 3) as f32]
+                })
+                .collect();
+            triangle_mesh(positions, vec![0, 3, 4, 0, 4, 1, 4, 5, 2, 4, 2, 1])
+        };
+        let mut base = grid(0.0);
+        apply_morph_targets(&mut base, &[(&grid(1.0), 0.5)]).unwrap();
+        for (i, p) in mesh_positions(&base).enumerate() {
+            assert_eq!(p.y, if i == 4 { 0.5 } else { 0.0 });
+        }
+
+        let mut small = triangle_mesh(vec![[0.0; 3]; 3], vec![0, 1, 2]);
+        assert!(apply_morph_targets(&mut small, &[(&base, 1.0)]).is_err());
+    }
+
+    #[test]
+    fn mirror_mesh_across_yz_plane() {
+        let mut mesh = mesh_with_transform(&cube(), &Transform::from_xyz(2.0, 0.0, 0.0));
+        let original = mesh.clone();
+        mirror_mesh(&mut mesh, Vec3::ZERO, Vec3::X);
+
+        for (p, q) in mesh_positions(&mesh).zip(mesh_positions(&original)) {
+            assert!(p.abs_diff_eq(*q * Vec3::new(-1.0, 1.0, 1.0), 1e-6));
+        }
+        for (n, m) in mesh_normals(&mesh).zip(mesh_normals(&original)) {
+            assert!(n.abs_diff_eq(*m * Vec3::new(-1.0, 1.0, 1.0), 1e-6));
+        }
+        assert!(winding_matches_normals(&mesh));
+        assert!((mesh_volume(&mesh) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn mesh_with_transform_negative_scale_keeps_faces_outward() {
+        let transform = Transform::from_scale(Vec3::new(-1.0, 1.0, 1.0));
+        let mesh = mesh_with_transform(&cube(), &transform);
+        assert!(winding_matches_normals(&mesh));
+        assert!((mesh_volume(&mesh) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn radial_array_is_symmetric_about_axis() {
+        let mesh = radial_array(&cube(), 4, Vec3::Y, 3.0);
+        assert_eq!(mesh_len(&mesh), mesh_len(&cube()) * 4);
+        let (min, max) = mesh_aabb(&mesh).unwrap();
+        assert!(min.abs_diff_eq(Vec3::new(-3.5, -0.5, -3.5), 1e-4));
+        assert!(max.abs_diff_eq(Vec3::new(3.5, 0.5, 3.5), 1e-4));
+    }
+
+    #[test]
+    fn laplacian_smooth_reduces_noise_on_sphere() {
+        let mut mesh = Sphere::new(1.0).mesh().ico(10).unwrap();
+        // Weld the UV seam so smoothing sees a closed surface
+        mesh.remove_attribute(Mesh::ATTRIBUTE_UV_0);
+        weld_vertices(&mut mesh, 1e-4);
+        let mut seed = 1u32;
+        map_positions(&mut mesh, |p| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            let noise = (seed >> 8) as f32 / (1 << 24) as f32 - 0.5;
+            p * (1.0 + noise * 0.1)
+        });
+        let deviation = |mesh: &Mesh| {
+            mesh_positions(mesh)
+                .map(|p| (p.length() - 1.0).abs())
+                .sum::<f32>()
+                / mesh_len(mesh) as f32
+        };
+
+        let noisy = deviation(&mesh);
+        laplacian_smooth(&mut mesh, 2, 0.5, false);
+        assert!(deviation(&mesh) < noisy * 0.5);
+    }
+
+    #[test]
+    fn make_double_sided_adds_flipped_copy() {
+        let mesh = cube();
+        let double_sided = make_double_sided(&mesh);
+        assert_eq!(triangle_count(&double_sided), triangle_count(&mesh) * 2);
+
+        let normals = mesh_normals_slice(&double_sided);
+        let (front, back) = normals.split_at(mesh_len(&mesh));
+        for (front, back) in front.iter().zip(back) {
+            assert_eq!(*front, -*back);
+        }
+        // Both halves face the way their normals point
+        assert!(winding_matches_normals(&double_sided));
+    }
+
+    #[test]
+    fn mass_properties_unit_cube() {
+        let properties = mass_properties(&cube(), 2.0);
+        assert!((properties.mass - 2.0).abs() < 1e-5);
+        assert!(properties.center_of_mass.abs_diff_eq(Vec3::ZERO, 1e-5));
+        // m * (a^2 + b^2) / 12 about each axis
+        let expected = Mat3::from_diagonal(Vec3::splat(2.0 * 2.0 / 12.0));
+        assert!(properties.inertia.abs_diff_eq(expected, 1e-5));
+    }
+
+    #[test]
+    fn fix_inverted_normals_flips_one_face() {
+        let mut mesh = cube();
+        let expected = mesh_normals(&mesh).copied().collect::<Vec<_>>();
+        // Cuboid meshes have four vertices per face, invert the first face
+        for n in mesh_normals_mut(&mut mesh).take(4) {
+            *n = -*n;
+        }
+        fix_inverted_normals(&mut mesh);
+        assert_eq!(mesh_normals(&mesh).copied().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn harden_edges_cylinder_caps() {
+        let mut mesh = Mesh::from(Cylinder::new(1.0, 2.0).mesh().resolution(16));
+        // Share vertices between the caps and the side so only harden_edges splits them
+        mesh.remove_attribute(Mesh::ATTRIBUTE_NORMAL);
+        mesh.remove_attribute(Mesh::ATTRIBUTE_UV_0);
+        weld_vertices(&mut mesh, 1e-4);
+        let welded_len = mesh_len(&mesh);
+        harden_edges(&mut mesh, 30.0);
+
+        // The caps are fans over the rim, so every vertex splits into a cap and a side copy
+        assert_eq!(welded_len, 2 * 16);
+        assert_eq!(mesh_len(&mesh), 4 * 16);
+        for (p, n) in mesh_positions(&mesh).zip(mesh_normals(&mesh)) {
+            let radial = Vec3::new(p.x, 0.0, p.z).normalize_or_zero();
+            let is_cap = n.abs_diff_eq(Vec3::Y * p.y.signum(), 1e-5);
+            // The side stays smooth, a face normal would be 11.25 degrees off the radial direction
+            let is_side = n.y.abs() < 1e-5 && n.dot(radial) > 0.995;
+            assert!(is_cap || is_side, "{p} {n}");
+        }
+    }
+
+    #[test]
+    fn weld_vertices_zero_epsilon_only_merges_exact_duplicates() {
+        let mut mesh = triangle_mesh(
+            vec![
+                [1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0],
+                [-1.0, -2.0, -3.0],
+                [1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0],
+                [-7.0, -8.0, -9.0],
+            ],
+            vec![0, 1, 2, 3, 4, 5],
+        );
+        weld_vertices(&mut mesh, 0.0);
+        assert_eq!(mesh_len(&mesh), 4);
+        assert_eq!(
+            mesh.indices().unwrap().iter().collect::<Vec<_>>(),
+            [0, 1, 2, 0, 1, 3]
+        );
+    }
+
+    #[test]
+    fn voxelize_rejects_degenerate_cell_sizes() {
+        for cell_size in [0.0, -1.0, f32::NAN, f32::INFINITY, 1e-30] {
+            let grid = voxelize(&cube(), cell_size, VoxelizeMode::Surface);
+            assert_eq!(grid.dims, UVec3::ZERO);
+            assert!(grid.occupied.is_empty());
+        }
+    }
+
+    #[test]
+    fn raycast_hits_millimetre_scale_triangle() {
+        let mesh = triangle_mesh(
+            vec![[0.0, 0.0, 0.0], [2e-4, 0.0, 0.0], [0.0, 2e-4, 0.0]],
+            vec![0, 1, 2],
+        );
+        let origin = Vec3::new(5e-5, 5e-5, 1.0);
+        let hit = raycast_mesh(&mesh, origin, Vec3::NEG_Z).unwrap();
+        assert_eq!(hit.triangle_index, 0);
+        assert!((hit.distance - 1.0).abs() < 1e-6);
+        assert!(hit.position.abs_diff_eq(Vec3::new(5e-5, 5e-5, 0.0), 1e-7));
+        let bvh_hit = MeshBvh::build(&mesh).raycast(origin, Vec3::NEG_Z).unwrap();
+        assert_eq!(bvh_hit.triangle_index, 0);
+
+        // a ray in the triangle's plane is still rejected
+        assert!(raycast_mesh(&mesh, Vec3::new(-1.0, 5e-5, 0.0), Vec3::X).is_none());
+    }
+
+    #[test]
+    fn remove_degenerate_triangles_drops_slivers_and_repeated_indices() {
+        let positions = vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [2.0, 0.0, 0.0],
+            [3.0, 1e-6, 0.0],
+            [4.0, 0.0, 0.0],
+        ];
+        // valid, sliver, repeated index, valid
+        let indices = [0, 1, 2, 3, 4, 5, 0, 1, 1, 2, 1, 0];
+        for u16_indices in [true, false] {
+            let mut mesh = triangle_mesh(positions.clone(), indices.to_vec());
+            if u16_indices {
+                convert_indices_u16(&mut mesh).unwrap();
+            }
+            remove_degenerate_triangles(&mut mesh, 1e-4);
+            match mesh.indices().unwrap() {
+                Indices::U16(indices) => {
+                    assert!(u16_indices);
+                    assert_eq!(indices, &[0, 1, 2, 2, 1, 0]);
+                }
+                Indices::U32(indices) => {
+                    assert!(!u16_indices);
+                    assert_eq!(indices, &[0, 1, 2, 2, 1, 0]);
+                }
+            }
+            assert_eq!(mesh_len(&mesh), positions.len());
+        }
+    }
+
+    #[test]
+    fn sample_sdf_grid_handles_large_empty_resolution() {
+        let bounds = (Vec3::splat(-1.0), Vec3::splat(1.0));
+        let values = sample_sdf_grid(&cube(), bounds, UVec3::new(70000, 70000, 0));
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn obj_round_trip() {
+        let mesh = cube();
+        let mut obj = Vec::new();
+        write_obj(&mesh, &mut obj).unwrap();
+        let read = read_obj(&mut obj.as_slice()).unwrap();
+
+        let indices = triangle_list_indices(&mesh);
+        let read_indices = triangle_list_indices(&read);
+        assert_eq!(read_indices.len(), indices.len());
+        for (i, j) in indices.iter().zip(&read_indices) {
+            assert_eq!(
+                mesh_positions_slice(&read)[*j],
+                mesh_positions_slice(&mesh)[*i]
+            );
+            assert_eq!(mesh_normals_slice(&read)[*j], mesh_normals_slice(&mesh)[*i]);
+            let uv = mesh_uvs_slice(&mesh)[*i];
+            assert!(mesh_uvs_slice(&read)[*j].abs_diff_eq(uv, 1e-6));
+        }
+    }
+
+    #[test]
+    fn read_obj_defaults_missing_v_coordinate() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvt 0.5\nvt 0.25 1\nf 1/1 2/2 3/1\n";
+        let mesh = read_obj(&mut obj.as_bytes()).unwrap();
+        assert_eq!(
+            mesh_uvs_slice(&mesh),
+            [
+                Vec2::new(0.5, 1.0),
+                Vec2::new(0.25, 0.0),
+                Vec2::new(0.5, 1.0)
+            ]
+        );
+    }
 }